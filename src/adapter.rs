@@ -0,0 +1,449 @@
+//! Adapters that wrap an [`Executor`].
+
+use crate::{
+    CancellableTask, DetachableTask, Executor, InspectableTask, Priority, PriorityExecutor,
+};
+
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::sync::Arc;
+
+use core::convert::Infallible;
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+
+#[cfg(feature = "alloc")]
+mod balanced;
+#[cfg(feature = "std")]
+mod bounded;
+mod budgeted;
+#[cfg(feature = "std")]
+mod catch_unwind;
+#[cfg(feature = "alloc")]
+mod hooked;
+#[cfg(feature = "alloc")]
+mod instrumented;
+#[cfg(feature = "std")]
+mod scheduled;
+#[cfg(feature = "std")]
+mod supervised;
+#[cfg(feature = "std")]
+mod timeout;
+#[cfg(feature = "tracing")]
+mod traced;
+#[cfg(feature = "alloc")]
+mod with_token;
+
+#[cfg(feature = "alloc")]
+pub use balanced::{Balanced, BalancedFuture};
+#[cfg(feature = "std")]
+pub use bounded::{Bounded, BoundedError, BoundedFuture};
+pub use budgeted::{Budgeted, BudgetedFuture};
+#[cfg(feature = "std")]
+pub use catch_unwind::{CatchUnwind, CatchUnwindFuture};
+#[cfg(feature = "alloc")]
+pub use hooked::{Hooked, HookedFuture, TaskId};
+#[cfg(feature = "alloc")]
+pub use instrumented::{Instrumented, InstrumentedFuture, Metrics};
+#[cfg(feature = "std")]
+pub use scheduled::{Delayed, ScheduledExecutor};
+#[cfg(feature = "std")]
+pub use supervised::{NoBackoff, Supervised, SupervisedFuture, SupervisorError};
+#[cfg(feature = "std")]
+pub use timeout::{Elapsed, Timeout, TimeoutFuture};
+#[cfg(feature = "tracing")]
+pub use traced::Traced;
+#[cfg(feature = "alloc")]
+pub use with_token::{WithToken, WithTokenFuture};
+
+/// Executor that converts the spawn error of another executor.
+///
+/// Created by [`ExecutorExt::map_err`](crate::ExecutorExt::map_err).
+#[derive(Clone, Copy)]
+pub struct MapErr<E, M> {
+    exec: E,
+    map: M,
+}
+
+impl<E: fmt::Debug, M> fmt::Debug for MapErr<E, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapErr").field("exec", &self.exec).finish()
+    }
+}
+
+impl<E, M> MapErr<E, M> {
+    pub(crate) fn new(exec: E, map: M) -> Self {
+        Self { exec, map }
+    }
+
+    /// Get a reference to the inner executor.
+    pub fn get_ref(&self) -> &E {
+        &self.exec
+    }
+
+    /// Convert to the inner executor.
+    pub fn into_inner(self) -> E {
+        self.exec
+    }
+}
+
+impl<F: Future, E: Executor<F>, M: Fn(E::Error) -> Err, Err> Executor<F> for MapErr<E, M> {
+    type Task = E::Task;
+    type Error = Err;
+
+    #[inline]
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        self.exec.try_spawn(future).map_err(&self.map)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn try_spawn_all(
+        &self,
+        futures: alloc::vec::Vec<F>,
+    ) -> Result<alloc::vec::Vec<Self::Task>, Self::Error> {
+        self.exec.try_spawn_all(futures).map_err(&self.map)
+    }
+}
+
+/// Executor that spawns futures by calling a closure.
+///
+/// This is useful for one-off executors in tests, or for thin wrappers around a runtime's
+/// spawn function.
+#[derive(Clone, Copy)]
+pub struct ExecutorFn<C> {
+    spawn: C,
+}
+
+impl<C> fmt::Debug for ExecutorFn<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ExecutorFn { .. }")
+    }
+}
+
+impl<C> ExecutorFn<C> {
+    /// Create an executor that spawns futures with `spawn`.
+    pub fn new(spawn: C) -> Self {
+        Self { spawn }
+    }
+
+    /// Convert to the inner closure.
+    pub fn into_inner(self) -> C {
+        self.spawn
+    }
+}
+
+impl<F, C, T, Err> Executor<F> for ExecutorFn<C>
+where
+    F: Future,
+    C: Fn(F) -> Result<T, Err>,
+    T: Future<Output = F::Output>,
+{
+    type Task = T;
+    type Error = Err;
+
+    #[inline]
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        (self.spawn)(future)
+    }
+}
+
+/// Executor that panics if the inner executor fails to spawn a future.
+///
+/// Created by [`ExecutorExt::infallible`](crate::ExecutorExt::infallible).
+#[derive(Debug, Clone, Copy)]
+pub struct Unwrapped<E> {
+    exec: E,
+}
+
+impl<E> Unwrapped<E> {
+    pub(crate) fn new(exec: E) -> Self {
+        Self { exec }
+    }
+
+    /// Get a reference to the inner executor.
+    pub fn get_ref(&self) -> &E {
+        &self.exec
+    }
+
+    /// Convert to the inner executor.
+    pub fn into_inner(self) -> E {
+        self.exec
+    }
+}
+
+impl<F: Future, E: Executor<F>> Executor<F> for Unwrapped<E>
+where
+    E::Error: fmt::Debug,
+{
+    type Task = E::Task;
+    type Error = Infallible;
+
+    #[inline]
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        match self.exec.try_spawn(future) {
+            Ok(task) => Ok(task),
+            Err(err) => panic!("failed to spawn future: {:?}", err),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn try_spawn_all(
+        &self,
+        futures: alloc::vec::Vec<F>,
+    ) -> Result<alloc::vec::Vec<Self::Task>, Self::Error> {
+        match self.exec.try_spawn_all(futures) {
+            Ok(tasks) => Ok(tasks),
+            Err(err) => panic!("failed to spawn future: {:?}", err),
+        }
+    }
+}
+
+/// Executor that spawns every task with the same priority.
+///
+/// Created by [`ExecutorExt::with_priority`](crate::ExecutorExt::with_priority).
+#[derive(Debug, Clone, Copy)]
+pub struct WithPriority<E> {
+    exec: E,
+    priority: Priority,
+}
+
+impl<E> WithPriority<E> {
+    pub(crate) fn new(exec: E, priority: Priority) -> Self {
+        Self { exec, priority }
+    }
+
+    /// Get the priority given to spawned tasks.
+    pub fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    /// Get a reference to the inner executor.
+    pub fn get_ref(&self) -> &E {
+        &self.exec
+    }
+
+    /// Convert to the inner executor.
+    pub fn into_inner(self) -> E {
+        self.exec
+    }
+}
+
+impl<F: Future, E: PriorityExecutor<F>> Executor<F> for WithPriority<E> {
+    type Task = E::Task;
+    type Error = E::Error;
+
+    #[inline]
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        self.exec.try_spawn_with_priority(future, self.priority)
+    }
+}
+
+/// Executor that names every task it spawns.
+///
+/// Created by [`ExecutorExt::named`](crate::ExecutorExt::named).
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct Named<E> {
+    exec: E,
+    name: String,
+}
+
+#[cfg(feature = "alloc")]
+impl<E> Named<E> {
+    pub(crate) fn new(exec: E, name: String) -> Self {
+        Self { exec, name }
+    }
+
+    /// Get the name given to spawned tasks.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get a reference to the inner executor.
+    pub fn get_ref(&self) -> &E {
+        &self.exec
+    }
+
+    /// Convert to the inner executor.
+    pub fn into_inner(self) -> E {
+        self.exec
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F: Future, E: for<'n> crate::MetadataExecutor<F, &'n str>> Executor<F> for Named<E> {
+    type Task = E::Task;
+    type Error = E::Error;
+
+    #[inline]
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        self.exec.try_spawn_with(future, &self.name)
+    }
+}
+
+/// Executor that falls back to a secondary executor if the primary one fails to spawn.
+///
+/// Created by [`ExecutorExt::or_else`](crate::ExecutorExt::or_else).
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+pub struct Fallback<A, B> {
+    primary: A,
+    secondary: B,
+}
+
+#[cfg(feature = "std")]
+impl<A, B> Fallback<A, B> {
+    pub(crate) fn new(primary: A, secondary: B) -> Self {
+        Self { primary, secondary }
+    }
+
+    /// Get a reference to the primary executor.
+    pub fn primary(&self) -> &A {
+        &self.primary
+    }
+
+    /// Get a reference to the secondary executor.
+    pub fn secondary(&self) -> &B {
+        &self.secondary
+    }
+
+    /// Convert to the primary and secondary executors.
+    pub fn into_inner(self) -> (A, B) {
+        (self.primary, self.secondary)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: Future, A: Executor<Handoff<F>>, B: Executor<F>> Executor<F> for Fallback<A, B> {
+    type Task = Either<A::Task, B::Task>;
+    type Error = B::Error;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        let slot = Arc::new(Mutex::new(Some(future)));
+
+        match self
+            .primary
+            .try_spawn(Handoff(HandoffState::Waiting(slot.clone())))
+        {
+            Ok(task) => Ok(Either::Left(task)),
+            Err(_) => {
+                let future = slot
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .take()
+                    .expect("primary executor polled a future it failed to spawn");
+                self.secondary.try_spawn(future).map(Either::Right)
+            }
+        }
+    }
+}
+
+/// The future spawned onto the primary executor of a [`Fallback`].
+///
+/// The original future is retrieved on the first poll, so that it can be handed to the
+/// secondary executor if spawning fails.
+#[cfg(feature = "std")]
+pub struct Handoff<F>(HandoffState<F>);
+
+#[cfg(feature = "std")]
+enum HandoffState<F> {
+    Waiting(Arc<Mutex<Option<F>>>),
+    Running(Pin<Box<F>>),
+}
+
+#[cfg(feature = "std")]
+impl<F> fmt::Debug for Handoff<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Handoff { .. }")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: Future> Future for Handoff<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let HandoffState::Waiting(slot) = &self.0 {
+            let future = slot
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .take()
+                .expect("future was already handed to the secondary executor");
+            self.0 = HandoffState::Running(Box::pin(future));
+        }
+
+        match &mut self.0 {
+            HandoffState::Running(future) => future.as_mut().poll(cx),
+            HandoffState::Waiting(_) => unreachable!(),
+        }
+    }
+}
+
+/// A task that is one of two task types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Either<L, R> {
+    /// The first task type.
+    Left(L),
+
+    /// The second task type.
+    Right(R),
+}
+
+impl<T, L: Future<Output = T>, R: Future<Output = T>> Future for Either<L, R> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: The variants are never moved out of the pinned enum.
+        unsafe {
+            match self.get_unchecked_mut() {
+                Either::Left(left) => Pin::new_unchecked(left).poll(cx),
+                Either::Right(right) => Pin::new_unchecked(right).poll(cx),
+            }
+        }
+    }
+}
+
+impl<L: CancellableTask, R: CancellableTask<Output = L::Output>> CancellableTask for Either<L, R> {
+    type Cancel<'a>
+        = Either<L::Cancel<'a>, R::Cancel<'a>>
+    where
+        Self: 'a;
+
+    fn cancel<'a>(self) -> Self::Cancel<'a>
+    where
+        Self: 'a,
+    {
+        match self {
+            Either::Left(left) => Either::Left(left.cancel()),
+            Either::Right(right) => Either::Right(right.cancel()),
+        }
+    }
+}
+
+impl<L: DetachableTask, R: DetachableTask<Output = L::Output>> DetachableTask for Either<L, R> {
+    fn detach(self) {
+        match self {
+            Either::Left(left) => left.detach(),
+            Either::Right(right) => right.detach(),
+        }
+    }
+}
+
+impl<L: InspectableTask, R: InspectableTask<Output = L::Output>> InspectableTask for Either<L, R> {
+    fn is_finished(&self) -> bool {
+        match self {
+            Either::Left(left) => left.is_finished(),
+            Either::Right(right) => right.is_finished(),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Spreading tasks across several executors.
+
+use crate::Executor;
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::{Context, Poll};
+
+use pin_project_lite::pin_project;
+
+/// Executor that distributes spawned tasks across several executors.
+///
+/// This can present a group of executors, such as one per worker thread in a thread-per-core
+/// setup, as a single [`Executor`]. Tasks are handed out in round-robin order by default, or
+/// to the executor with the fewest running tasks when created with
+/// [`Balanced::least_loaded`].
+pub struct Balanced<E> {
+    execs: Vec<E>,
+    loads: Vec<Arc<AtomicUsize>>,
+    next: AtomicUsize,
+    least_loaded: bool,
+}
+
+impl<E: fmt::Debug> fmt::Debug for Balanced<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Balanced")
+            .field("execs", &self.execs)
+            .field("least_loaded", &self.least_loaded)
+            .finish()
+    }
+}
+
+impl<E> Balanced<E> {
+    /// Create a `Balanced` that hands out tasks in round-robin order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `execs` is empty.
+    pub fn new(execs: impl IntoIterator<Item = E>) -> Self {
+        Self::with_strategy(execs, false)
+    }
+
+    /// Create a `Balanced` that hands each task to the executor with the fewest running tasks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `execs` is empty.
+    pub fn least_loaded(execs: impl IntoIterator<Item = E>) -> Self {
+        Self::with_strategy(execs, true)
+    }
+
+    fn with_strategy(execs: impl IntoIterator<Item = E>, least_loaded: bool) -> Self {
+        let execs = execs.into_iter().collect::<Vec<_>>();
+        assert!(!execs.is_empty(), "Balanced needs at least one executor");
+
+        Self {
+            loads: execs.iter().map(|_| Arc::default()).collect(),
+            execs,
+            next: AtomicUsize::new(0),
+            least_loaded,
+        }
+    }
+
+    /// Get the number of tasks spawned onto the executor at `index` that are still running.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn load(&self, index: usize) -> usize {
+        self.loads[index].load(Ordering::Acquire)
+    }
+
+    /// Get the inner executors.
+    pub fn executors(&self) -> &[E] {
+        &self.execs
+    }
+
+    /// Convert to the inner executors.
+    pub fn into_inner(self) -> Vec<E> {
+        self.execs
+    }
+
+    /// Pick the executor for the next task.
+    fn pick(&self) -> usize {
+        if self.least_loaded {
+            // Start the scan at a rotating offset so that ties are spread out.
+            let start = self.next.fetch_add(1, Ordering::Relaxed);
+            (0..self.execs.len())
+                .map(|i| (start.wrapping_add(i)) % self.execs.len())
+                .min_by_key(|&i| self.load(i))
+                .unwrap()
+        } else {
+            self.next.fetch_add(1, Ordering::Relaxed) % self.execs.len()
+        }
+    }
+}
+
+impl<F: Future, E: Executor<BalancedFuture<F>>> Executor<F> for Balanced<E> {
+    type Task = E::Task;
+    type Error = E::Error;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        let index = self.pick();
+        let load = self.loads[index].clone();
+        load.fetch_add(1, Ordering::AcqRel);
+
+        self.execs[index].try_spawn(BalancedFuture {
+            future,
+            _guard: LoadGuard(load),
+        })
+    }
+}
+
+pin_project! {
+    /// The future spawned by a [`Balanced`] executor.
+    ///
+    /// Counts towards the load of its executor until it is dropped.
+    pub struct BalancedFuture<F> {
+        #[pin]
+        future: F,
+        _guard: LoadGuard,
+    }
+}
+
+impl<F> fmt::Debug for BalancedFuture<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BalancedFuture { .. }")
+    }
+}
+
+impl<F: Future> Future for BalancedFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().future.poll(cx)
+    }
+}
+
+/// Decrements the load of an executor when dropped.
+struct LoadGuard(Arc<AtomicUsize>);
+
+impl Drop for LoadGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
@@ -0,0 +1,321 @@
+//! Limiting the number of live tasks.
+
+use crate::Executor;
+
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll, Waker};
+
+use pin_project_lite::pin_project;
+
+use std::sync::{Mutex, MutexGuard};
+
+/// Executor that limits the number of tasks that can be alive at once.
+///
+/// A task stops counting against the limit once it completes or is cancelled.
+#[derive(Debug, Clone)]
+pub struct Bounded<E> {
+    exec: E,
+    limiter: Arc<Limiter>,
+}
+
+#[derive(Debug)]
+struct Limiter {
+    limit: usize,
+    state: Mutex<LimiterState>,
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    active: usize,
+
+    /// Waiters, indexed by key.
+    slots: Vec<Slot>,
+
+    /// Keys of free slots.
+    free: Vec<usize>,
+
+    /// Keys of waiting slots, in the order they should be woken.
+    queue: VecDeque<usize>,
+}
+
+#[derive(Debug)]
+enum Slot {
+    Free,
+
+    /// Waiting in the queue for a permit.
+    Waiting(Waker),
+
+    /// Woken by a released permit, but not yet polled.
+    Notified,
+}
+
+impl LimiterState {
+    /// Take a permit if one is free.
+    fn take(&mut self, limit: usize) -> bool {
+        if self.active < limit {
+            self.active += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Pick the next waiter to wake, if any.
+    fn notify_one(&mut self) -> Option<Waker> {
+        let key = self.queue.pop_front()?;
+        match core::mem::replace(&mut self.slots[key], Slot::Notified) {
+            Slot::Waiting(waker) => Some(waker),
+            _ => unreachable!("only waiting slots are queued"),
+        }
+    }
+
+    /// Release a slot, returning what it held.
+    fn remove(&mut self, key: usize) -> Slot {
+        let slot = core::mem::replace(&mut self.slots[key], Slot::Free);
+        if let Slot::Waiting(_) = slot {
+            self.queue.retain(|&queued| queued != key);
+        }
+        self.free.push(key);
+        slot
+    }
+}
+
+impl Limiter {
+    fn lock(&self) -> MutexGuard<'_, LimiterState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn try_acquire(self: &Arc<Self>) -> Option<Permit> {
+        if self.lock().take(self.limit) {
+            Some(Permit {
+                limiter: self.clone(),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Future that waits for a permit, holding a single place in the queue.
+struct Acquire<'a> {
+    limiter: &'a Arc<Limiter>,
+    key: Option<usize>,
+}
+
+impl Future for Acquire<'_> {
+    type Output = Permit;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Permit> {
+        let limiter = self.limiter;
+        let mut state = limiter.lock();
+
+        if state.take(limiter.limit) {
+            if let Some(key) = self.key.take() {
+                state.remove(key);
+            }
+            return Poll::Ready(Permit {
+                limiter: limiter.clone(),
+            });
+        }
+
+        let waker = cx.waker().clone();
+        match self.key {
+            Some(key) => {
+                // A notified waiter lost the permit to someone else, so it goes back to the
+                // front of the queue.
+                if let Slot::Notified = state.slots[key] {
+                    state.queue.push_front(key);
+                }
+                state.slots[key] = Slot::Waiting(waker);
+            }
+            None => {
+                let key = match state.free.pop() {
+                    Some(key) => {
+                        state.slots[key] = Slot::Waiting(waker);
+                        key
+                    }
+                    None => {
+                        state.slots.push(Slot::Waiting(waker));
+                        state.slots.len() - 1
+                    }
+                };
+                state.queue.push_back(key);
+                self.key = Some(key);
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+impl Drop for Acquire<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key {
+            let waker = {
+                let mut state = self.limiter.lock();
+
+                // Pass on a wakeup that this waiter will no longer use.
+                match state.remove(key) {
+                    Slot::Notified => state.notify_one(),
+                    _ => None,
+                }
+            };
+
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Slot in a [`Bounded`] executor, released on drop.
+#[derive(Debug)]
+struct Permit {
+    limiter: Arc<Limiter>,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let waker = {
+            let mut state = self.limiter.lock();
+            state.active -= 1;
+            state.notify_one()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<E> Bounded<E> {
+    /// Create a new `Bounded` executor allowing at most `limit` live tasks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is zero.
+    pub fn new(exec: E, limit: usize) -> Self {
+        assert!(limit > 0, "limit must be greater than zero");
+
+        Self {
+            exec,
+            limiter: Arc::new(Limiter {
+                limit,
+                state: Mutex::new(LimiterState {
+                    active: 0,
+                    slots: Vec::new(),
+                    free: Vec::new(),
+                    queue: VecDeque::new(),
+                }),
+            }),
+        }
+    }
+
+    /// Get the maximum number of live tasks.
+    pub fn limit(&self) -> usize {
+        self.limiter.limit
+    }
+
+    /// Get the number of tasks that are currently alive.
+    pub fn in_flight(&self) -> usize {
+        self.limiter.lock().active
+    }
+
+    /// Get a reference to the inner executor.
+    pub fn get_ref(&self) -> &E {
+        &self.exec
+    }
+
+    /// Wait until there is capacity, then spawn the future.
+    pub async fn spawn<F: Future>(&self, future: F) -> Result<E::Task, E::Error>
+    where
+        E: Executor<BoundedFuture<F>>,
+    {
+        let permit = Acquire {
+            limiter: &self.limiter,
+            key: None,
+        }
+        .await;
+        self.exec.try_spawn(BoundedFuture {
+            future,
+            permit: Some(permit),
+        })
+    }
+}
+
+impl<F: Future, E: Executor<BoundedFuture<F>>> Executor<F> for Bounded<E> {
+    type Task = E::Task;
+    type Error = BoundedError<E::Error>;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        let permit = self.limiter.try_acquire().ok_or(BoundedError::Full)?;
+        self.exec
+            .try_spawn(BoundedFuture {
+                future,
+                permit: Some(permit),
+            })
+            .map_err(BoundedError::Spawn)
+    }
+}
+
+pin_project! {
+    /// The future spawned onto the inner executor of a [`Bounded`].
+    pub struct BoundedFuture<F> {
+        #[pin]
+        future: F,
+        permit: Option<Permit>,
+    }
+}
+
+impl<F> fmt::Debug for BoundedFuture<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BoundedFuture { .. }")
+    }
+}
+
+impl<F: Future> Future for BoundedFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let output = ready!(this.future.poll(cx));
+
+        // Release the slot as soon as the future completes.
+        *this.permit = None;
+        Poll::Ready(output)
+    }
+}
+
+/// Error returned by a [`Bounded`] executor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundedError<E> {
+    /// The maximum number of tasks are already alive.
+    Full,
+
+    /// The inner executor failed to spawn the future.
+    Spawn(E),
+}
+
+impl<E: fmt::Display> fmt::Display for BoundedError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoundedError::Full => f.write_str("too many tasks are already running"),
+            BoundedError::Spawn(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for BoundedError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BoundedError::Full => None,
+            BoundedError::Spawn(err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Making tasks yield to the executor regularly.
+
+use crate::Executor;
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use pin_project_lite::pin_project;
+
+/// Executor adapter whose tasks yield after being polled a number of times in a row.
+///
+/// Every spawned future is given a budget of polls. Once it has been polled that many times
+/// without completing, its next poll wakes the task and returns `Poll::Pending` instead, so
+/// that other tasks on a single-threaded executor get a chance to run.
+///
+/// Created by [`ExecutorExt::with_budget`](crate::ExecutorExt::with_budget).
+#[derive(Debug, Clone, Copy)]
+pub struct Budgeted<E> {
+    exec: E,
+    budget: usize,
+}
+
+impl<E> Budgeted<E> {
+    pub(crate) fn new(exec: E, budget: usize) -> Self {
+        assert!(budget > 0, "budget must be greater than zero");
+        Self { exec, budget }
+    }
+
+    /// Get the number of polls given to spawned tasks.
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Get a reference to the inner executor.
+    pub fn get_ref(&self) -> &E {
+        &self.exec
+    }
+
+    /// Convert to the inner executor.
+    pub fn into_inner(self) -> E {
+        self.exec
+    }
+
+    /// Try to spawn a future with a budget other than the one for this executor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `budget` is zero.
+    pub fn try_spawn_with_budget<F: Future>(
+        &self,
+        future: F,
+        budget: usize,
+    ) -> Result<E::Task, E::Error>
+    where
+        E: Executor<BudgetedFuture<F>>,
+    {
+        self.exec.try_spawn(BudgetedFuture::new(future, budget))
+    }
+}
+
+impl<F: Future, E: Executor<BudgetedFuture<F>>> Executor<F> for Budgeted<E> {
+    type Task = E::Task;
+    type Error = E::Error;
+
+    #[inline]
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        self.try_spawn_with_budget(future, self.budget)
+    }
+}
+
+pin_project! {
+    /// Future that yields to the executor after being polled a number of times in a row.
+    pub struct BudgetedFuture<F> {
+        #[pin]
+        future: F,
+        budget: usize,
+        remaining: usize,
+    }
+}
+
+impl<F> BudgetedFuture<F> {
+    /// Wrap a future to yield after every `budget` polls.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `budget` is zero.
+    pub fn new(future: F, budget: usize) -> Self {
+        assert!(budget > 0, "budget must be greater than zero");
+        Self {
+            future,
+            budget,
+            remaining: budget,
+        }
+    }
+}
+
+impl<F> fmt::Debug for BudgetedFuture<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BudgetedFuture { .. }")
+    }
+}
+
+impl<F: Future> Future for BudgetedFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        // Out of budget, so yield once and start over.
+        if *this.remaining == 0 {
+            *this.remaining = *this.budget;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        *this.remaining -= 1;
+        this.future.poll(cx)
+    }
+}
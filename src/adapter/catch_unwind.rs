@@ -0,0 +1,77 @@
+//! Catching panics in spawned tasks.
+
+use crate::Executor;
+
+use alloc::boxed::Box;
+
+use core::any::Any;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use pin_project_lite::pin_project;
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Executor adapter whose tasks resolve to an error instead of panicking.
+///
+/// Since the tasks produced by this adapter do not output `F::Output`, it cannot implement
+/// [`Executor<F>`]. Use [`CatchUnwind::try_spawn`] instead.
+#[derive(Debug, Clone)]
+pub struct CatchUnwind<E> {
+    exec: E,
+}
+
+impl<E> CatchUnwind<E> {
+    /// Create a new `CatchUnwind` executor.
+    pub fn new(exec: E) -> Self {
+        Self { exec }
+    }
+
+    /// Get a reference to the inner executor.
+    pub fn get_ref(&self) -> &E {
+        &self.exec
+    }
+
+    /// Convert to the inner executor.
+    pub fn into_inner(self) -> E {
+        self.exec
+    }
+
+    /// Try to spawn a future, catching any panics that occur while polling it.
+    pub fn try_spawn<F: Future>(&self, future: F) -> Result<E::Task, E::Error>
+    where
+        E: Executor<CatchUnwindFuture<F>>,
+    {
+        self.exec.try_spawn(CatchUnwindFuture { future })
+    }
+}
+
+pin_project! {
+    /// Future that catches panics while it is polled.
+    #[derive(Debug)]
+    pub struct CatchUnwindFuture<F> {
+        #[pin]
+        future: F,
+    }
+}
+
+impl<F> CatchUnwindFuture<F> {
+    /// Wrap a future to catch its panics.
+    pub fn new(future: F) -> Self {
+        Self { future }
+    }
+}
+
+impl<F: Future> Future for CatchUnwindFuture<F> {
+    type Output = Result<F::Output, Box<dyn Any + Send>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let future = self.project().future;
+        match catch_unwind(AssertUnwindSafe(|| future.poll(cx))) {
+            Ok(Poll::Pending) => Poll::Pending,
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! Running callbacks over the lifetime of spawned tasks.
+
+use crate::Executor;
+
+use alloc::sync::Arc;
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use core::task::{ready, Context, Poll};
+
+use pin_project_lite::pin_project;
+
+type Hook = Arc<dyn Fn(TaskId) + Send + Sync>;
+
+/// Executor that calls hooks when tasks are spawned, complete, or are cancelled.
+///
+/// Every task is given a [`TaskId`] that is unique for this executor and its clones. This is
+/// a building block for logging, leak detection and profiling that works with any backend.
+#[derive(Clone)]
+pub struct Hooked<E> {
+    exec: E,
+    next_id: Arc<AtomicUsize>,
+    on_spawn: Option<Hook>,
+    on_complete: Option<Hook>,
+    on_cancel: Option<Hook>,
+}
+
+impl<E: fmt::Debug> fmt::Debug for Hooked<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hooked").field("exec", &self.exec).finish()
+    }
+}
+
+impl<E> Hooked<E> {
+    /// Create a new `Hooked` executor with no hooks.
+    pub fn new(exec: E) -> Self {
+        Self {
+            exec,
+            next_id: Arc::default(),
+            on_spawn: None,
+            on_complete: None,
+            on_cancel: None,
+        }
+    }
+
+    /// Call `hook` once a task has been spawned onto the inner executor.
+    ///
+    /// This is not called if the inner executor fails to spawn the task. Executors that run
+    /// tasks inline may complete a task before this is called.
+    pub fn on_spawn(mut self, hook: impl Fn(TaskId) + Send + Sync + 'static) -> Self {
+        self.on_spawn = Some(Arc::new(hook));
+        self
+    }
+
+    /// Call `hook` once a task has run to completion.
+    pub fn on_complete(mut self, hook: impl Fn(TaskId) + Send + Sync + 'static) -> Self {
+        self.on_complete = Some(Arc::new(hook));
+        self
+    }
+
+    /// Call `hook` once a task is dropped before completing.
+    ///
+    /// This is not called if the inner executor fails to spawn the task.
+    pub fn on_cancel(mut self, hook: impl Fn(TaskId) + Send + Sync + 'static) -> Self {
+        self.on_cancel = Some(Arc::new(hook));
+        self
+    }
+
+    /// Get a reference to the inner executor.
+    pub fn get_ref(&self) -> &E {
+        &self.exec
+    }
+
+    /// Convert to the inner executor.
+    pub fn into_inner(self) -> E {
+        self.exec
+    }
+}
+
+impl<F: Future, E: Executor<HookedFuture<F>>> Executor<F> for Hooked<E> {
+    type Task = E::Task;
+    type Error = E::Error;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        let id = TaskId(self.next_id.fetch_add(1, Ordering::Relaxed));
+
+        // The inner executor drops the future if spawning fails, so the cancel hook is only
+        // armed once it has succeeded.
+        let on_cancel = self
+            .on_cancel
+            .clone()
+            .map(|hook| (hook, Arc::new(AtomicBool::new(false))));
+        let armed = on_cancel.as_ref().map(|(_, armed)| armed.clone());
+
+        let guard = Guard {
+            id,
+            on_complete: self.on_complete.clone(),
+            on_cancel,
+            done: false,
+        };
+        let task = self.exec.try_spawn(HookedFuture { future, guard })?;
+
+        if let Some(armed) = armed {
+            armed.store(true, Ordering::Release);
+        }
+        if let Some(hook) = &self.on_spawn {
+            hook(id);
+        }
+        Ok(task)
+    }
+}
+
+/// An identifier for a task spawned onto a [`Hooked`] executor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaskId(usize);
+
+pin_project! {
+    /// The future spawned onto the inner executor of a [`Hooked`].
+    pub struct HookedFuture<F> {
+        #[pin]
+        future: F,
+        guard: Guard,
+    }
+}
+
+impl<F> fmt::Debug for HookedFuture<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("HookedFuture { .. }")
+    }
+}
+
+impl<F: Future> Future for HookedFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let output = ready!(this.future.poll(cx));
+
+        if !this.guard.done {
+            this.guard.done = true;
+            if let Some(hook) = &this.guard.on_complete {
+                hook(this.guard.id);
+            }
+        }
+
+        Poll::Ready(output)
+    }
+}
+
+/// Calls the cancel hook if the task is dropped before completing.
+struct Guard {
+    id: TaskId,
+    on_complete: Option<Hook>,
+    on_cancel: Option<(Hook, Arc<AtomicBool>)>,
+    done: bool,
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        if !self.done {
+            if let Some((hook, armed)) = &self.on_cancel {
+                if armed.load(Ordering::Acquire) {
+                    hook(self.id);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Counting the tasks spawned onto an executor.
+
+use crate::Executor;
+
+use alloc::sync::Arc;
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::{ready, Context, Poll};
+
+use pin_project_lite::pin_project;
+
+/// Executor that keeps count of the tasks spawned through it.
+#[derive(Debug, Clone)]
+pub struct Instrumented<E> {
+    exec: E,
+    counters: Arc<Counters>,
+}
+
+/// Cancelled tasks are not counted directly, but are derived from the other counters.
+#[derive(Debug, Default)]
+struct Counters {
+    spawned: AtomicUsize,
+    running: AtomicUsize,
+    completed: AtomicUsize,
+}
+
+/// A snapshot of the task counts of an [`Instrumented`] executor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Metrics {
+    /// The total number of tasks that have been spawned.
+    pub spawned: usize,
+
+    /// The number of tasks that have neither completed nor been cancelled.
+    pub running: usize,
+
+    /// The number of tasks that ran to completion.
+    pub completed: usize,
+
+    /// The number of tasks that were dropped before completing.
+    pub cancelled: usize,
+}
+
+impl<E> Instrumented<E> {
+    /// Create a new `Instrumented` executor.
+    pub fn new(exec: E) -> Self {
+        Self {
+            exec,
+            counters: Arc::default(),
+        }
+    }
+
+    /// Get a snapshot of the current task counts.
+    pub fn metrics(&self) -> Metrics {
+        let completed = self.counters.completed.load(Ordering::Relaxed);
+        let running = self.counters.running.load(Ordering::Relaxed);
+        let spawned = self.counters.spawned.load(Ordering::Relaxed);
+
+        Metrics {
+            spawned,
+            running,
+            completed,
+            cancelled: spawned.saturating_sub(running + completed),
+        }
+    }
+
+    /// Get a reference to the inner executor.
+    pub fn get_ref(&self) -> &E {
+        &self.exec
+    }
+
+    /// Convert to the inner executor.
+    pub fn into_inner(self) -> E {
+        self.exec
+    }
+}
+
+impl<F: Future, E: Executor<InstrumentedFuture<F>>> Executor<F> for Instrumented<E> {
+    type Task = E::Task;
+    type Error = E::Error;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        // Count the task up front, since it may complete before `try_spawn` returns.
+        self.counters.spawned.fetch_add(1, Ordering::Relaxed);
+        self.counters.running.fetch_add(1, Ordering::Relaxed);
+
+        let guard = Guard {
+            counters: self.counters.clone(),
+            done: false,
+        };
+        self.exec
+            .try_spawn(InstrumentedFuture { future, guard })
+            .inspect_err(|_| {
+                // The guard stops counting the task as running once the future is dropped.
+                self.counters.spawned.fetch_sub(1, Ordering::Relaxed);
+            })
+    }
+}
+
+pin_project! {
+    /// The future spawned onto the inner executor of an [`Instrumented`].
+    pub struct InstrumentedFuture<F> {
+        #[pin]
+        future: F,
+        guard: Guard,
+    }
+}
+
+impl<F> fmt::Debug for InstrumentedFuture<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("InstrumentedFuture { .. }")
+    }
+}
+
+impl<F: Future> Future for InstrumentedFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let output = ready!(this.future.poll(cx));
+
+        if !this.guard.done {
+            this.guard.done = true;
+            this.guard
+                .counters
+                .completed
+                .fetch_add(1, Ordering::Relaxed);
+            this.guard.counters.running.fetch_sub(1, Ordering::Relaxed);
+        }
+
+        Poll::Ready(output)
+    }
+}
+
+/// Stops counting the task as running if it is dropped before completing.
+struct Guard {
+    counters: Arc<Counters>,
+    done: bool,
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        if !self.done {
+            self.counters.running.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! Spawning futures that start after a delay.
+
+use crate::{Executor, Timer};
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+use core::time::Duration;
+
+use pin_project_lite::pin_project;
+
+use std::time::Instant;
+
+/// Executor adapter that can delay the start of spawned futures.
+///
+/// The task is spawned right away, but the future is not polled until the delay has passed.
+/// Cancelling the task before then drops the future without it ever running. Futures spawned
+/// through [`Executor`] start immediately.
+#[derive(Debug, Clone)]
+pub struct ScheduledExecutor<E, T> {
+    exec: E,
+    timer: T,
+}
+
+impl<E, T: Timer> ScheduledExecutor<E, T> {
+    /// Create a new `ScheduledExecutor` that delays futures using `timer`.
+    pub fn new(exec: E, timer: T) -> Self {
+        Self { exec, timer }
+    }
+
+    /// Get a reference to the inner executor.
+    pub fn get_ref(&self) -> &E {
+        &self.exec
+    }
+
+    /// Get a reference to the timer.
+    pub fn timer(&self) -> &T {
+        &self.timer
+    }
+
+    /// Convert to the inner executor and timer.
+    pub fn into_inner(self) -> (E, T) {
+        (self.exec, self.timer)
+    }
+
+    /// Spawn a future that starts once `delay` has passed.
+    pub fn spawn_after<F: Future>(&self, delay: Duration, future: F) -> Result<E::Task, E::Error>
+    where
+        E: Executor<Delayed<F, T::Sleep>>,
+    {
+        self.exec
+            .try_spawn(Delayed::new(future, self.timer.sleep(delay)))
+    }
+
+    /// Spawn a future that starts once `deadline` is reached.
+    pub fn spawn_at<F: Future>(&self, deadline: Instant, future: F) -> Result<E::Task, E::Error>
+    where
+        E: Executor<Delayed<F, T::Sleep>>,
+    {
+        self.exec
+            .try_spawn(Delayed::new(future, self.timer.sleep_until(deadline)))
+    }
+}
+
+impl<F: Future, E: Executor<F>, T> Executor<F> for ScheduledExecutor<E, T> {
+    type Task = E::Task;
+    type Error = E::Error;
+
+    #[inline]
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        self.exec.try_spawn(future)
+    }
+}
+
+pin_project! {
+    /// Future that waits for a timer before polling the inner future.
+    pub struct Delayed<F, S> {
+        #[pin]
+        future: F,
+        #[pin]
+        sleep: S,
+        started: bool,
+    }
+}
+
+impl<F, S> Delayed<F, S> {
+    /// Wrap a future to start once `sleep` completes.
+    pub fn new(future: F, sleep: S) -> Self {
+        Self {
+            future,
+            sleep,
+            started: false,
+        }
+    }
+}
+
+impl<F, S> fmt::Debug for Delayed<F, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Delayed { .. }")
+    }
+}
+
+impl<F: Future, S: Future<Output = ()>> Future for Delayed<F, S> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if !*this.started {
+            ready!(this.sleep.poll(cx));
+            *this.started = true;
+        }
+
+        this.future.poll(cx)
+    }
+}
@@ -0,0 +1,212 @@
+//! Restarting tasks that panic or are cancelled.
+
+use super::CatchUnwindFuture;
+use crate::Executor;
+
+use core::fmt;
+use core::future::{ready, Future, Ready};
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+
+use pin_project_lite::pin_project;
+
+/// The default backoff for [`Supervised`], which restarts immediately.
+pub type NoBackoff = fn(usize) -> Ready<()>;
+
+/// Executor adapter that restarts tasks if they panic or are cancelled.
+///
+/// Instead of a single future, [`Supervised::try_spawn`] takes a closure that creates a new
+/// future for each attempt. Each attempt is spawned as its own task, watched over by a
+/// supervisor task that is spawned onto the same executor.
+#[derive(Clone)]
+pub struct Supervised<E, B = NoBackoff> {
+    exec: E,
+    max_restarts: usize,
+    backoff: B,
+}
+
+impl<E: fmt::Debug, B> fmt::Debug for Supervised<E, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Supervised")
+            .field("exec", &self.exec)
+            .field("max_restarts", &self.max_restarts)
+            .finish()
+    }
+}
+
+impl<E> Supervised<E> {
+    /// Create a new `Supervised` executor that restarts tasks without limit.
+    pub fn new(exec: E) -> Self {
+        Self {
+            exec,
+            max_restarts: usize::MAX,
+            backoff: |_| ready(()),
+        }
+    }
+}
+
+impl<E, B> Supervised<E, B> {
+    /// Set the number of times a task may be restarted before giving up.
+    pub fn max_restarts(mut self, max_restarts: usize) -> Self {
+        self.max_restarts = max_restarts;
+        self
+    }
+
+    /// Wait for the future returned by `backoff` before each restart.
+    ///
+    /// `backoff` is passed the number of restarts so far, starting from zero.
+    pub fn backoff<B2: FnMut(usize) -> BF + Clone, BF: Future<Output = ()>>(
+        self,
+        backoff: B2,
+    ) -> Supervised<E, B2> {
+        Supervised {
+            exec: self.exec,
+            max_restarts: self.max_restarts,
+            backoff,
+        }
+    }
+
+    /// Get a reference to the inner executor.
+    pub fn get_ref(&self) -> &E {
+        &self.exec
+    }
+
+    /// Convert to the inner executor.
+    pub fn into_inner(self) -> E {
+        self.exec
+    }
+
+    /// Try to spawn a supervised task whose attempts are created by `make`.
+    #[allow(clippy::type_complexity)]
+    pub fn try_spawn<M, F, BF>(
+        &self,
+        make: M,
+    ) -> Result<
+        <E as Executor<SupervisedFuture<E, M, F, B, BF>>>::Task,
+        <E as Executor<SupervisedFuture<E, M, F, B, BF>>>::Error,
+    >
+    where
+        M: FnMut() -> F,
+        F: Future,
+        B: FnMut(usize) -> BF + Clone,
+        BF: Future<Output = ()>,
+        E: Executor<CatchUnwindFuture<F>> + Executor<SupervisedFuture<E, M, F, B, BF>> + Clone,
+    {
+        self.exec.try_spawn(SupervisedFuture {
+            exec: self.exec.clone(),
+            make,
+            backoff: self.backoff.clone(),
+            restarts: 0,
+            max_restarts: self.max_restarts,
+            state: State::Start,
+        })
+    }
+}
+
+pin_project! {
+    /// Future that supervises the attempts of a task spawned by [`Supervised`].
+    pub struct SupervisedFuture<E: Executor<CatchUnwindFuture<F>>, M, F: Future, B, BF> {
+        exec: E,
+        make: M,
+        backoff: B,
+        restarts: usize,
+        max_restarts: usize,
+        #[pin]
+        state: State<CatchUnwindFuture<E::Task>, BF>,
+    }
+}
+
+pin_project! {
+    #[project = StateProj]
+    enum State<T, BF> {
+        Start,
+        Running { #[pin] task: T },
+        Backoff { #[pin] delay: BF },
+    }
+}
+
+impl<E: Executor<CatchUnwindFuture<F>>, M, F: Future, B, BF> fmt::Debug
+    for SupervisedFuture<E, M, F, B, BF>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SupervisedFuture { .. }")
+    }
+}
+
+impl<E, M, F, B, BF> Future for SupervisedFuture<E, M, F, B, BF>
+where
+    E: Executor<CatchUnwindFuture<F>>,
+    M: FnMut() -> F,
+    F: Future,
+    B: FnMut(usize) -> BF,
+    BF: Future<Output = ()>,
+{
+    type Output = Result<F::Output, SupervisorError<E::Error>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        loop {
+            match this.state.as_mut().project() {
+                StateProj::Start => {
+                    let future = CatchUnwindFuture::new((this.make)());
+                    match this.exec.try_spawn(future) {
+                        Ok(task) => this.state.set(State::Running {
+                            task: CatchUnwindFuture::new(task),
+                        }),
+                        Err(err) => return Poll::Ready(Err(SupervisorError::Spawn(err))),
+                    }
+                }
+
+                StateProj::Running { task } => {
+                    // The outer `Err` means that the task itself panicked on poll, which is how
+                    // most tasks report being cancelled.
+                    if let Ok(Ok(output)) = ready!(task.poll(cx)) {
+                        return Poll::Ready(Ok(output));
+                    }
+
+                    if *this.restarts >= *this.max_restarts {
+                        return Poll::Ready(Err(SupervisorError::RestartLimit));
+                    }
+
+                    let delay = (this.backoff)(*this.restarts);
+                    *this.restarts += 1;
+                    this.state.set(State::Backoff { delay });
+                }
+
+                StateProj::Backoff { delay } => {
+                    ready!(delay.poll(cx));
+                    this.state.set(State::Start);
+                }
+            }
+        }
+    }
+}
+
+/// Error returned by tasks spawned through [`Supervised`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupervisorError<E> {
+    /// The task failed more times than it was allowed to restart.
+    RestartLimit,
+
+    /// Spawning an attempt failed.
+    Spawn(E),
+}
+
+impl<E: fmt::Display> fmt::Display for SupervisorError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SupervisorError::RestartLimit => f.write_str("task exceeded its restart limit"),
+            SupervisorError::Spawn(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for SupervisorError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SupervisorError::RestartLimit => None,
+            SupervisorError::Spawn(err) => Some(err),
+        }
+    }
+}
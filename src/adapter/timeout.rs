@@ -0,0 +1,113 @@
+//! Cancelling tasks that run for too long.
+
+use crate::{Executor, Timer};
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use pin_project_lite::pin_project;
+
+use std::time::Instant;
+
+/// Executor adapter whose tasks are cancelled if they run past a deadline.
+///
+/// The deadline is measured from when the task is spawned. Since the tasks produced by this
+/// adapter do not output `F::Output`, it cannot implement [`Executor<F>`]. Use
+/// [`Timeout::try_spawn`] instead.
+///
+/// Created by [`ExecutorExt::with_timeout`](crate::ExecutorExt::with_timeout).
+#[derive(Debug, Clone)]
+pub struct Timeout<E, T> {
+    exec: E,
+    duration: Duration,
+    timer: T,
+}
+
+impl<E, T: Timer> Timeout<E, T> {
+    pub(crate) fn new(exec: E, duration: Duration, timer: T) -> Self {
+        Self {
+            exec,
+            duration,
+            timer,
+        }
+    }
+
+    /// Get the time that spawned tasks are allowed to run for.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Get a reference to the inner executor.
+    pub fn get_ref(&self) -> &E {
+        &self.exec
+    }
+
+    /// Convert to the inner executor.
+    pub fn into_inner(self) -> E {
+        self.exec
+    }
+
+    /// Try to spawn a future that is cancelled if it runs past the deadline.
+    pub fn try_spawn<F: Future>(&self, future: F) -> Result<E::Task, E::Error>
+    where
+        E: Executor<TimeoutFuture<F, T::Sleep>>,
+    {
+        let deadline = Instant::now() + self.duration;
+        self.exec
+            .try_spawn(TimeoutFuture::new(future, self.timer.sleep_until(deadline)))
+    }
+}
+
+pin_project! {
+    /// Future that resolves to [`Elapsed`] if a timer fires before it completes.
+    pub struct TimeoutFuture<F, S> {
+        #[pin]
+        future: F,
+        #[pin]
+        sleep: S,
+    }
+}
+
+impl<F, S> TimeoutFuture<F, S> {
+    /// Wrap a future to stop once `sleep` completes.
+    pub fn new(future: F, sleep: S) -> Self {
+        Self { future, sleep }
+    }
+}
+
+impl<F, S> fmt::Debug for TimeoutFuture<F, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TimeoutFuture { .. }")
+    }
+}
+
+impl<F: Future, S: Future<Output = ()>> Future for TimeoutFuture<F, S> {
+    type Output = Result<F::Output, Elapsed>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if let Poll::Ready(output) = this.future.poll(cx) {
+            return Poll::Ready(Ok(output));
+        }
+
+        this.sleep.poll(cx).map(|()| Err(Elapsed { _private: () }))
+    }
+}
+
+/// Error returned by a task that ran past its deadline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed {
+    _private: (),
+}
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("task ran past its deadline")
+    }
+}
+
+impl std::error::Error for Elapsed {}
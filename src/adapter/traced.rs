@@ -0,0 +1,62 @@
+//! Propagating `tracing` spans into spawned tasks.
+
+use crate::Executor;
+
+use core::future::Future;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use tracing::instrument::{Instrument, Instrumented};
+use tracing::Span;
+
+/// Executor that runs spawned futures inside the span that was current when they were spawned.
+#[derive(Debug, Clone)]
+pub struct Traced<E> {
+    exec: E,
+    task_spans: bool,
+}
+
+impl<E> Traced<E> {
+    /// Create a new `Traced` executor.
+    pub fn new(exec: E) -> Self {
+        Self {
+            exec,
+            task_spans: false,
+        }
+    }
+
+    /// Set whether each task gets its own `task` span with a unique `task.id` field.
+    ///
+    /// The task span is a child of the span that was current when the task was spawned.
+    pub fn task_spans(mut self, task_spans: bool) -> Self {
+        self.task_spans = task_spans;
+        self
+    }
+
+    /// Get a reference to the inner executor.
+    pub fn get_ref(&self) -> &E {
+        &self.exec
+    }
+
+    /// Convert to the inner executor.
+    pub fn into_inner(self) -> E {
+        self.exec
+    }
+}
+
+impl<F: Future, E: Executor<Instrumented<F>>> Executor<F> for Traced<E> {
+    type Task = E::Task;
+    type Error = E::Error;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
+        let span = if self.task_spans {
+            let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+            tracing::info_span!("task", task.id = id)
+        } else {
+            Span::current()
+        };
+
+        self.exec.try_spawn(future.instrument(span))
+    }
+}
@@ -0,0 +1,94 @@
+//! Tying spawned tasks to a cancellation token.
+
+use crate::token::Registration;
+use crate::{CancellationToken, Executor};
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use pin_project_lite::pin_project;
+
+/// Executor adapter whose tasks resolve to `None` once a [`CancellationToken`] is cancelled.
+///
+/// Since the tasks produced by this adapter do not output `F::Output`, it cannot implement
+/// [`Executor<F>`]. Use [`WithToken::try_spawn`] instead.
+///
+/// Created by [`ExecutorExt::with_token`](crate::ExecutorExt::with_token).
+#[derive(Debug, Clone)]
+pub struct WithToken<E> {
+    exec: E,
+    token: CancellationToken,
+}
+
+impl<E> WithToken<E> {
+    pub(crate) fn new(exec: E, token: CancellationToken) -> Self {
+        Self { exec, token }
+    }
+
+    /// Get the token that cancels spawned tasks.
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    /// Get a reference to the inner executor.
+    pub fn get_ref(&self) -> &E {
+        &self.exec
+    }
+
+    /// Convert to the inner executor.
+    pub fn into_inner(self) -> E {
+        self.exec
+    }
+
+    /// Try to spawn a future that stops early once the token is cancelled.
+    pub fn try_spawn<F: Future>(&self, future: F) -> Result<E::Task, E::Error>
+    where
+        E: Executor<WithTokenFuture<F>>,
+    {
+        self.exec.try_spawn(WithTokenFuture {
+            future,
+            registration: Registration::new(self.token.clone()),
+        })
+    }
+}
+
+pin_project! {
+    /// Future that resolves to `None` once a [`CancellationToken`] is cancelled.
+    pub struct WithTokenFuture<F> {
+        #[pin]
+        future: F,
+        registration: Registration,
+    }
+}
+
+impl<F> WithTokenFuture<F> {
+    /// Wrap a future to stop early once `token` is cancelled.
+    pub fn new(future: F, token: CancellationToken) -> Self {
+        Self {
+            future,
+            registration: Registration::new(token),
+        }
+    }
+}
+
+impl<F> fmt::Debug for WithTokenFuture<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WithTokenFuture { .. }")
+    }
+}
+
+impl<F: Future> Future for WithTokenFuture<F> {
+    type Output = Option<F::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if this.registration.poll_cancelled(cx) {
+            return Poll::Ready(None);
+        }
+
+        this.future.poll(cx).map(Some)
+    }
+}
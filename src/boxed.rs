@@ -3,123 +3,436 @@
 use crate::Executor;
 
 use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
 
+use core::cell::Cell;
+use core::convert::Infallible;
+use core::fmt;
 use core::future::Future;
 use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use std::sync::Mutex;
+
+/// The error type of boxed executors.
+///
+/// This erases the error of the underlying executor, which can be recovered by downcasting.
+pub struct SpawnError {
+    inner: Box<dyn std::error::Error + Send + Sync + 'static>,
+}
+
+impl SpawnError {
+    /// Create a new `SpawnError` wrapping `err`.
+    pub fn new<E: std::error::Error + Send + Sync + 'static>(err: E) -> Self {
+        Self {
+            inner: Box::new(err),
+        }
+    }
+
+    /// Tell whether the underlying error is of type `E`.
+    pub fn is<E: std::error::Error + 'static>(&self) -> bool {
+        self.inner.is::<E>()
+    }
+
+    /// Get a reference to the underlying error if it is of type `E`.
+    pub fn downcast_ref<E: std::error::Error + 'static>(&self) -> Option<&E> {
+        self.inner.downcast_ref()
+    }
+
+    /// Convert to the underlying error if it is of type `E`.
+    pub fn downcast<E: std::error::Error + 'static>(self) -> Result<E, Self> {
+        match self.inner.downcast() {
+            Ok(err) => Ok(*err),
+            Err(inner) => Err(Self { inner }),
+        }
+    }
+
+    /// Convert to the underlying boxed error.
+    pub fn into_inner(self) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+        self.inner
+    }
+}
+
+impl fmt::Debug for SpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.inner, f)
+    }
+}
+
+impl fmt::Display for SpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.inner, f)
+    }
+}
+
+impl std::error::Error for SpawnError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.inner.source()
+    }
+}
+
+/// The error type of thread-unsafe boxed executors.
+///
+/// Like [`SpawnError`], but the underlying error does not need to be `Send` or `Sync`.
+pub struct LocalSpawnError {
+    inner: Box<dyn std::error::Error + 'static>,
+}
+
+impl LocalSpawnError {
+    /// Create a new `LocalSpawnError` wrapping `err`.
+    pub fn new<E: std::error::Error + 'static>(err: E) -> Self {
+        Self {
+            inner: Box::new(err),
+        }
+    }
+
+    /// Tell whether the underlying error is of type `E`.
+    pub fn is<E: std::error::Error + 'static>(&self) -> bool {
+        self.inner.is::<E>()
+    }
+
+    /// Get a reference to the underlying error if it is of type `E`.
+    pub fn downcast_ref<E: std::error::Error + 'static>(&self) -> Option<&E> {
+        self.inner.downcast_ref()
+    }
+
+    /// Convert to the underlying error if it is of type `E`.
+    pub fn downcast<E: std::error::Error + 'static>(self) -> Result<E, Self> {
+        match self.inner.downcast() {
+            Ok(err) => Ok(*err),
+            Err(inner) => Err(Self { inner }),
+        }
+    }
+
+    /// Convert to the underlying boxed error.
+    pub fn into_inner(self) -> Box<dyn std::error::Error + 'static> {
+        self.inner
+    }
+}
+
+impl fmt::Debug for LocalSpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.inner, f)
+    }
+}
+
+impl fmt::Display for LocalSpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.inner, f)
+    }
+}
+
+impl std::error::Error for LocalSpawnError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.inner.source()
+    }
+}
+
+type BoxFuture<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+type LocalBoxFuture<'a> = Pin<Box<dyn Future<Output = ()> + 'a>>;
 
 /// A dynamic [`Executor`] allocated on the heap.
-#[allow(clippy::type_complexity)]
-pub struct BoxedExecutor<'a, T> {
-    inner: Box<
-        dyn Executor<
-                Pin<Box<dyn Future<Output = T> + Send + 'a>>,
-                Task = Pin<Box<dyn Future<Output = T> + Send + 'a>>,
-                Error = Box<dyn std::error::Error + Send + 'a>,
-            > + Send
-            + 'a,
-    >,
+///
+/// Futures with any `Send` output type can be spawned onto it. The executor is reference
+/// counted, so cloning it is cheap.
+#[derive(Clone)]
+pub struct BoxedExecutor<'a> {
+    inner: Arc<dyn DynExecutor<'a> + Send + Sync + 'a>,
 }
 
-impl<'a, T> BoxedExecutor<'a, T> {
+impl<'a> BoxedExecutor<'a> {
     /// Create a new `BoxedExecutor`.
-    pub fn new<E: Executor<Pin<Box<dyn Future<Output = T> + Send + 'a>>> + Send + 'a>(
-        exec: E,
-    ) -> Self
+    pub fn new<E: DynExecutor<'a> + Send + Sync + 'a>(exec: E) -> Self {
+        BoxedExecutor {
+            inner: Arc::new(exec),
+        }
+    }
+
+    /// Create a `BoxedExecutor` that shares an executor that is already reference counted.
+    ///
+    /// Unlike [`new`](Self::new), this does not allocate another layer of indirection.
+    pub fn shared<E: DynExecutor<'a> + Send + Sync + 'a>(exec: Arc<E>) -> Self {
+        BoxedExecutor { inner: exec }
+    }
+}
+
+impl<'a, E: DynExecutor<'a> + Send + Sync + 'a> From<Arc<E>> for BoxedExecutor<'a> {
+    fn from(exec: Arc<E>) -> Self {
+        Self::shared(exec)
+    }
+}
+
+impl<'a, F: Future + Send + 'a> Executor<F> for BoxedExecutor<'a>
+where
+    F::Output: Send + 'a,
+{
+    type Task = DynTask<'a, F::Output>;
+    type Error = SpawnError;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        let exec: &(dyn DynExecutor<'a> + 'a) = &*self.inner;
+        exec.try_spawn(future)
+    }
+}
+
+/// A dynamic [`Executor`] allocated on the heap, but thread-unsafe.
+///
+/// Futures with any output type can be spawned onto it. The executor is reference counted, so
+/// cloning it is cheap.
+#[derive(Clone)]
+pub struct LocalBoxedExecutor<'a> {
+    inner: Rc<
+        dyn Executor<LocalBoxFuture<'a>, Task = LocalBoxFuture<'a>, Error = LocalSpawnError> + 'a,
+    >,
+}
+
+impl<'a> LocalBoxedExecutor<'a> {
+    /// Create a new `LocalBoxedExecutor`.
+    pub fn new<E: Executor<LocalBoxFuture<'a>> + 'a>(exec: E) -> Self
     where
-        E::Task: Send + 'a,
-        E::Error: std::error::Error + Send + 'a,
+        E::Task: 'a,
+        E::Error: std::error::Error + 'static,
     {
         // Inner executor that wraps the task in a box.
         struct BoxingExecutor<E>(E);
 
-        impl<'a, T, E: Executor<Pin<Box<dyn Future<Output = T> + Send + 'a>>>>
-            Executor<Pin<Box<dyn Future<Output = T> + Send + 'a>>> for BoxingExecutor<E>
+        impl<'a, E: Executor<LocalBoxFuture<'a>>> Executor<LocalBoxFuture<'a>> for BoxingExecutor<E>
         where
-            E::Task: Send + 'a,
-            E::Error: std::error::Error + Send + 'a,
+            E::Task: 'a,
+            E::Error: std::error::Error + 'static,
         {
-            type Task = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
-            type Error = Box<dyn std::error::Error + Send + 'a>;
+            type Task = LocalBoxFuture<'a>;
+            type Error = LocalSpawnError;
 
-            fn try_spawn(
-                &self,
-                future: Pin<Box<dyn Future<Output = T> + Send + 'a>>,
-            ) -> Result<Self::Task, Self::Error> {
+            fn try_spawn(&self, future: LocalBoxFuture<'a>) -> Result<Self::Task, Self::Error> {
                 match self.0.try_spawn(future) {
                     Ok(task) => Ok(Box::pin(task)),
-                    Err(err) => Err(Box::new(err)),
+                    Err(err) => Err(LocalSpawnError::new(err)),
                 }
             }
         }
 
-        BoxedExecutor {
-            inner: Box::new(BoxingExecutor(exec)),
+        LocalBoxedExecutor {
+            inner: Rc::new(BoxingExecutor(exec)),
         }
     }
 }
 
-impl<'a, T, F: Future<Output = T> + Send + 'a> Executor<F> for BoxedExecutor<'a, T> {
-    type Task = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
-    type Error = Box<dyn std::error::Error + Send + 'a>;
+impl<'a, F: Future + 'a> Executor<F> for LocalBoxedExecutor<'a>
+where
+    F::Output: 'a,
+{
+    type Task = LocalBoxedTask<'a, F::Output>;
+    type Error = LocalSpawnError;
 
     fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
-        self.inner.try_spawn(Box::pin(future))
+        let slot = Rc::new(Cell::new(None));
+        let task = self.inner.try_spawn(Box::pin({
+            let slot = slot.clone();
+            async move {
+                slot.set(Some(future.await));
+            }
+        }))?;
+
+        Ok(LocalBoxedTask { task, slot })
     }
 }
 
-/// A dynamic [`Executor`] allocated on the heap, but thread-unsafe.
-#[allow(clippy::type_complexity)]
-pub struct LocalBoxedExecutor<'a, T> {
-    inner: Box<
-        dyn Executor<
-                Pin<Box<dyn Future<Output = T> + 'a>>,
-                Task = Pin<Box<dyn Future<Output = T> + 'a>>,
-                Error = Box<dyn std::error::Error + 'a>,
-            > + 'a,
+/// The task type spawned by a [`LocalBoxedExecutor`].
+pub struct LocalBoxedTask<'a, T> {
+    task: LocalBoxFuture<'a>,
+    slot: Rc<Cell<Option<T>>>,
+}
+
+impl<T> Future for LocalBoxedTask<'_, T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.task.as_mut().poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(()) => Poll::Ready(self.slot.take().expect("task polled after completion")),
+        }
+    }
+}
+
+/// A dynamic [`Executor`] allocated on the heap that never fails to spawn.
+///
+/// Unlike [`BoxedExecutor`], this can only be created from executors whose error type is
+/// [`Infallible`], and preserves that guarantee.
+#[derive(Clone)]
+pub struct InfallibleBoxedExecutor<'a> {
+    inner: Arc<
+        dyn Executor<BoxFuture<'a>, Task = BoxFuture<'a>, Error = Infallible> + Send + Sync + 'a,
     >,
 }
 
-impl<'a, T> LocalBoxedExecutor<'a, T> {
-    /// Create a new `BoxedExecutor`.
-    pub fn new<E: Executor<Pin<Box<dyn Future<Output = T> + 'a>>> + 'a>(exec: E) -> Self
+impl<'a> InfallibleBoxedExecutor<'a> {
+    /// Create a new `InfallibleBoxedExecutor`.
+    pub fn new<E: Executor<BoxFuture<'a>, Error = Infallible> + Send + Sync + 'a>(exec: E) -> Self
+    where
+        E::Task: Send + 'a,
+    {
+        // Inner executor that wraps the task in a box.
+        struct BoxingExecutor<E>(E);
+
+        impl<'a, E: Executor<BoxFuture<'a>, Error = Infallible>> Executor<BoxFuture<'a>>
+            for BoxingExecutor<E>
+        where
+            E::Task: Send + 'a,
+        {
+            type Task = BoxFuture<'a>;
+            type Error = Infallible;
+
+            fn try_spawn(&self, future: BoxFuture<'a>) -> Result<Self::Task, Self::Error> {
+                self.0.try_spawn(future).map(|task| Box::pin(task) as _)
+            }
+        }
+
+        InfallibleBoxedExecutor {
+            inner: Arc::new(BoxingExecutor(exec)),
+        }
+    }
+}
+
+impl<'a, F: Future + Send + 'a> Executor<F> for InfallibleBoxedExecutor<'a>
+where
+    F::Output: Send + 'a,
+{
+    type Task = DynTask<'a, F::Output>;
+    type Error = Infallible;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        DynTask::spawn(future, |future| self.inner.try_spawn(future))
+    }
+}
+
+/// A dynamic [`Executor`] allocated on the heap that never fails to spawn, but thread-unsafe.
+#[derive(Clone)]
+pub struct InfallibleLocalBoxedExecutor<'a> {
+    inner: Rc<dyn Executor<LocalBoxFuture<'a>, Task = LocalBoxFuture<'a>, Error = Infallible> + 'a>,
+}
+
+impl<'a> InfallibleLocalBoxedExecutor<'a> {
+    /// Create a new `InfallibleLocalBoxedExecutor`.
+    pub fn new<E: Executor<LocalBoxFuture<'a>, Error = Infallible> + 'a>(exec: E) -> Self
     where
         E::Task: 'a,
-        E::Error: std::error::Error + 'a,
     {
         // Inner executor that wraps the task in a box.
         struct BoxingExecutor<E>(E);
 
-        impl<'a, T, E: Executor<Pin<Box<dyn Future<Output = T> + 'a>>>>
-            Executor<Pin<Box<dyn Future<Output = T> + 'a>>> for BoxingExecutor<E>
+        impl<'a, E: Executor<LocalBoxFuture<'a>, Error = Infallible>> Executor<LocalBoxFuture<'a>>
+            for BoxingExecutor<E>
         where
             E::Task: 'a,
-            E::Error: std::error::Error + 'a,
         {
-            type Task = Pin<Box<dyn Future<Output = T> + 'a>>;
-            type Error = Box<dyn std::error::Error + 'a>;
+            type Task = LocalBoxFuture<'a>;
+            type Error = Infallible;
 
-            fn try_spawn(
-                &self,
-                future: Pin<Box<dyn Future<Output = T> + 'a>>,
-            ) -> Result<Self::Task, Self::Error> {
-                match self.0.try_spawn(future) {
-                    Ok(task) => Ok(Box::pin(task)),
-                    Err(err) => Err(Box::new(err)),
-                }
+            fn try_spawn(&self, future: LocalBoxFuture<'a>) -> Result<Self::Task, Self::Error> {
+                self.0.try_spawn(future).map(|task| Box::pin(task) as _)
             }
         }
 
-        LocalBoxedExecutor {
-            inner: Box::new(BoxingExecutor(exec)),
+        InfallibleLocalBoxedExecutor {
+            inner: Rc::new(BoxingExecutor(exec)),
         }
     }
 }
 
-impl<'a, T, F: Future<Output = T> + 'a> Executor<F> for LocalBoxedExecutor<'a, T> {
-    type Task = Pin<Box<dyn Future<Output = T> + 'a>>;
-    type Error = Box<dyn std::error::Error + 'a>;
+impl<'a, F: Future + 'a> Executor<F> for InfallibleLocalBoxedExecutor<'a>
+where
+    F::Output: 'a,
+{
+    type Task = LocalBoxedTask<'a, F::Output>;
+    type Error = Infallible;
 
     fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
-        self.inner.try_spawn(Box::pin(future))
+        let slot = Rc::new(Cell::new(None));
+        let task = self.inner.try_spawn(Box::pin({
+            let slot = slot.clone();
+            async move {
+                slot.set(Some(future.await));
+            }
+        }))?;
+
+        Ok(LocalBoxedTask { task, slot })
+    }
+}
+
+/// An object-safe executor for boxed futures with no output.
+///
+/// This is implemented for every [`Executor`] of such futures. In turn, `dyn DynExecutor<'a>`
+/// implements [`Executor`] for any `Send + 'a` future.
+pub trait DynExecutor<'a> {
+    /// Try to spawn a boxed future on this executor.
+    fn try_spawn_dyn(&self, future: BoxFuture<'a>) -> Result<BoxFuture<'a>, SpawnError>;
+}
+
+impl<'a, E: Executor<BoxFuture<'a>>> DynExecutor<'a> for E
+where
+    E::Task: Send + 'a,
+    E::Error: std::error::Error + Send + Sync + 'static,
+{
+    fn try_spawn_dyn(&self, future: BoxFuture<'a>) -> Result<BoxFuture<'a>, SpawnError> {
+        match self.try_spawn(future) {
+            Ok(task) => Ok(Box::pin(task)),
+            Err(err) => Err(SpawnError::new(err)),
+        }
+    }
+}
+
+impl<'a, F: Future + Send + 'a> Executor<F> for dyn DynExecutor<'a> + '_
+where
+    F::Output: Send + 'a,
+{
+    type Task = DynTask<'a, F::Output>;
+    type Error = SpawnError;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        DynTask::spawn(future, |future| self.try_spawn_dyn(future))
+    }
+}
+
+/// The task type spawned by a [`DynExecutor`] or a [`BoxedExecutor`].
+pub struct DynTask<'a, T> {
+    task: BoxFuture<'a>,
+    slot: Arc<Mutex<Option<T>>>,
+}
+
+impl<'a, T: Send + 'a> DynTask<'a, T> {
+    /// Box `future` so that it stores its output in the task, and spawn it with `spawn`.
+    fn spawn<F: Future<Output = T> + Send + 'a, Err>(
+        future: F,
+        spawn: impl FnOnce(BoxFuture<'a>) -> Result<BoxFuture<'a>, Err>,
+    ) -> Result<Self, Err> {
+        let slot = Arc::new(Mutex::new(None));
+        let task = spawn(Box::pin({
+            let slot = slot.clone();
+            async move {
+                let output = future.await;
+                *slot.lock().unwrap() = Some(output);
+            }
+        }))?;
+
+        Ok(DynTask { task, slot })
+    }
+}
+
+impl<T> Future for DynTask<'_, T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.task.as_mut().poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(()) => Poll::Ready(
+                self.slot
+                    .lock()
+                    .unwrap()
+                    .take()
+                    .expect("task polled after completion"),
+            ),
+        }
     }
 }
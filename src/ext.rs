@@ -0,0 +1,1487 @@
+//! Combinators built on top of [`Executor`].
+
+use crate::spin::SpinLock;
+use crate::{CancellableTask, Executor, ExecutorMut, UnwrapInfallible};
+
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::task::Wake;
+use alloc::vec::Vec;
+
+use core::cell::RefCell;
+use core::convert::Infallible;
+use core::fmt;
+use core::future::{poll_fn, Future};
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll, Waker};
+
+use atomic_waker::AtomicWaker;
+use futures_core::Stream;
+
+/// A dynamic group of tasks spawned onto an executor.
+///
+/// Futures can be added at any time, and their outputs are yielded in completion order.
+/// Dropping the set cancels every task still in it.
+///
+/// Each task gets its own waker, so only the tasks that were woken are polled again.
+pub struct TaskSet<E: Executor<F>, F: Future> {
+    exec: E,
+
+    /// Tasks, indexed by key. Removed slots are left empty.
+    slots: Vec<Option<Entry<E::Task>>>,
+
+    /// Keys of empty slots.
+    free: Vec<usize>,
+
+    len: usize,
+    ready: Arc<ReadyQueue>,
+}
+
+/// A task in a [`TaskSet`], with the waker that queues it when woken.
+struct Entry<T> {
+    task: Pin<Box<T>>,
+    notify: Arc<TaskWaker>,
+    waker: Waker,
+}
+
+/// Keys of the tasks in a [`TaskSet`] that have been woken.
+struct ReadyQueue {
+    keys: SpinLock<VecDeque<usize>>,
+    waker: AtomicWaker,
+}
+
+/// Waker for a single task in a [`TaskSet`].
+struct TaskWaker {
+    key: usize,
+
+    /// Set while the key is in the ready queue.
+    queued: AtomicBool,
+
+    ready: Arc<ReadyQueue>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        if !self.queued.swap(true, Ordering::AcqRel) {
+            self.ready.keys.lock().push_back(self.key);
+            self.ready.waker.wake();
+        }
+    }
+}
+
+impl<E: Executor<F> + fmt::Debug, F: Future> fmt::Debug for TaskSet<E, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskSet")
+            .field("exec", &self.exec)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl<E: Executor<F>, F: Future> TaskSet<E, F> {
+    /// Create a new, empty `TaskSet` that spawns onto `exec`.
+    pub fn new(exec: E) -> Self {
+        Self {
+            exec,
+            slots: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+            ready: Arc::new(ReadyQueue {
+                keys: SpinLock::new(VecDeque::new()),
+                waker: AtomicWaker::new(),
+            }),
+        }
+    }
+
+    /// Get a reference to the underlying executor.
+    pub fn executor(&self) -> &E {
+        &self.exec
+    }
+
+    /// Spawn a future onto the executor and add it to this set.
+    pub fn spawn(&mut self, future: F) -> Result<(), E::Error> {
+        self.spawn_keyed(future).map(drop)
+    }
+
+    /// Get the number of tasks in this set.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Tell whether this set contains no tasks.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Cancel every task in this set.
+    pub fn abort_all(&mut self) {
+        self.slots.clear();
+        self.free.clear();
+        self.len = 0;
+        self.ready.keys.lock().clear();
+    }
+
+    /// Wait for the next task to complete and return its output.
+    ///
+    /// Returns `None` if the set is empty.
+    pub async fn next(&mut self) -> Option<F::Output> {
+        poll_fn(|cx| self.poll_next(cx)).await
+    }
+
+    /// Poll for the next task to complete.
+    ///
+    /// Returns `Poll::Ready(None)` if the set is empty.
+    pub fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<F::Output>> {
+        self.poll_next_keyed(cx)
+            .map(|next| next.map(|(_, output)| output))
+    }
+
+    /// Spawn a future and return the key of its slot.
+    ///
+    /// Keys are reused once a task has been removed.
+    pub(crate) fn spawn_keyed(&mut self, future: F) -> Result<usize, E::Error> {
+        let task = Box::pin(self.exec.try_spawn(future)?);
+        let key = self.free.pop().unwrap_or(self.slots.len());
+
+        // New tasks start out queued, so that they are polled once.
+        let notify = Arc::new(TaskWaker {
+            key,
+            queued: AtomicBool::new(true),
+            ready: self.ready.clone(),
+        });
+        self.ready.keys.lock().push_back(key);
+
+        let entry = Entry {
+            task,
+            waker: Waker::from(notify.clone()),
+            notify,
+        };
+        match self.slots.get_mut(key) {
+            Some(slot) => *slot = Some(entry),
+            None => self.slots.push(Some(entry)),
+        }
+        self.len += 1;
+
+        Ok(key)
+    }
+
+    /// Cancel the task under `key`, returning whether there was one.
+    pub(crate) fn remove_keyed(&mut self, key: usize) -> bool {
+        match self.slots.get_mut(key).and_then(Option::take) {
+            Some(_) => {
+                self.free.push(key);
+                self.len -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Poll for the next task to complete, returning its key alongside its output.
+    pub(crate) fn poll_next_keyed(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<(usize, F::Output)>> {
+        if self.len == 0 {
+            return Poll::Ready(None);
+        }
+
+        self.ready.waker.register(cx.waker());
+
+        // Bound the work done in one call, in case tasks keep waking themselves.
+        for _ in 0..self.len {
+            let key = match self.ready.keys.lock().pop_front() {
+                Some(key) => key,
+                None => return Poll::Pending,
+            };
+
+            // The key may be stale if its task has since been removed.
+            let entry = match self.slots.get_mut(key) {
+                Some(Some(entry)) => entry,
+                _ => continue,
+            };
+
+            entry.notify.queued.store(false, Ordering::Release);
+            let mut task_cx = Context::from_waker(&entry.waker);
+            if let Poll::Ready(output) = entry.task.as_mut().poll(&mut task_cx) {
+                self.remove_keyed(key);
+                return Poll::Ready(Some((key, output)));
+            }
+        }
+
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// A keyed group of tasks spawned onto an executor.
+///
+/// Each key has at most one task. Inserting a future under a key that already has a task
+/// cancels the old task. Outputs are yielded in completion order alongside their keys.
+pub struct TaskMap<K, E: Executor<F>, F: Future> {
+    set: TaskSet<E, F>,
+
+    /// Keys, indexed by the slot of their task in `set`.
+    keys: Vec<Option<K>>,
+}
+
+impl<K, E: Executor<F> + fmt::Debug, F: Future> fmt::Debug for TaskMap<K, E, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskMap")
+            .field("exec", &self.set.exec)
+            .field("len", &self.set.len)
+            .finish()
+    }
+}
+
+impl<K: Eq, E: Executor<F>, F: Future> TaskMap<K, E, F> {
+    /// Create a new, empty `TaskMap` that spawns onto `exec`.
+    pub fn new(exec: E) -> Self {
+        Self {
+            set: TaskSet::new(exec),
+            keys: Vec::new(),
+        }
+    }
+
+    /// Get a reference to the underlying executor.
+    pub fn executor(&self) -> &E {
+        &self.set.exec
+    }
+
+    /// Spawn a future onto the executor under `key`.
+    ///
+    /// If a task was already running under `key`, it is cancelled. If spawning fails, the old
+    /// task is left in place.
+    pub fn insert(&mut self, key: K, future: F) -> Result<(), E::Error> {
+        let slot = self.set.spawn_keyed(future)?;
+        self.remove(&key);
+
+        if self.keys.len() <= slot {
+            self.keys.resize_with(slot + 1, || None);
+        }
+        self.keys[slot] = Some(key);
+        Ok(())
+    }
+
+    /// Cancel the task under `key`, returning whether there was one.
+    pub fn remove(&mut self, key: &K) -> bool {
+        match self.slot_of(key) {
+            Some(slot) => {
+                self.keys[slot] = None;
+                self.set.remove_keyed(slot)
+            }
+            None => false,
+        }
+    }
+
+    /// Tell whether there is a task under `key`.
+    pub fn contains(&self, key: &K) -> bool {
+        self.slot_of(key).is_some()
+    }
+
+    /// Get the number of tasks in this map.
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    /// Tell whether this map contains no tasks.
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    /// Cancel every task in this map.
+    pub fn abort_all(&mut self) {
+        self.set.abort_all();
+        self.keys.clear();
+    }
+
+    /// Wait for the next task to complete and return its key and output.
+    ///
+    /// Returns `None` if the map is empty.
+    pub async fn next(&mut self) -> Option<(K, F::Output)> {
+        poll_fn(|cx| self.poll_next(cx)).await
+    }
+
+    /// Poll for the next task to complete.
+    ///
+    /// Returns `Poll::Ready(None)` if the map is empty.
+    pub fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<(K, F::Output)>> {
+        self.set.poll_next_keyed(cx).map(|next| {
+            next.map(|(slot, output)| {
+                let key = self.keys[slot].take().expect("task without a key");
+                (key, output)
+            })
+        })
+    }
+
+    fn slot_of(&self, key: &K) -> Option<usize> {
+        self.keys.iter().position(|k| k.as_ref() == Some(key))
+    }
+}
+
+// The tasks are boxed and the other fields are never pinned.
+impl<K, E: Executor<F>, F: Future> Unpin for TaskMap<K, E, F> {}
+
+impl<K: Eq, E: Executor<F>, F: Future> Stream for TaskMap<K, E, F> {
+    type Item = (K, F::Output);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        TaskMap::poll_next(self.get_mut(), cx)
+    }
+}
+
+/// Tasks whose outputs are yielded in the order they were spawned.
+///
+/// Completed outputs are buffered until every task spawned before them has completed.
+pub(crate) struct OrderedTasks<E: Executor<F>, F: Future> {
+    exec: E,
+    slots: VecDeque<Slot<E::Task>>,
+}
+
+enum Slot<T: Future> {
+    Running(Pin<Box<T>>),
+    Done(T::Output),
+}
+
+impl<E: Executor<F>, F: Future> OrderedTasks<E, F> {
+    pub(crate) fn new(exec: E) -> Self {
+        Self {
+            exec,
+            slots: VecDeque::new(),
+        }
+    }
+
+    /// Get the number of tasks that are running or have outputs buffered.
+    pub(crate) fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub(crate) fn spawn(&mut self, future: F) -> Result<(), E::Error> {
+        let task = self.exec.try_spawn(future)?;
+        self.slots.push_back(Slot::Running(Box::pin(task)));
+        Ok(())
+    }
+
+    /// Poll for the output of the oldest task.
+    ///
+    /// Returns `Poll::Ready(None)` if there are no tasks.
+    pub(crate) fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<F::Output>> {
+        for slot in self.slots.iter_mut() {
+            if let Slot::Running(task) = slot {
+                if let Poll::Ready(output) = task.as_mut().poll(cx) {
+                    *slot = Slot::Done(output);
+                }
+            }
+        }
+
+        match self.slots.front() {
+            None => Poll::Ready(None),
+            Some(Slot::Running(_)) => Poll::Pending,
+            Some(Slot::Done(_)) => match self.slots.pop_front() {
+                Some(Slot::Done(output)) => Poll::Ready(Some(output)),
+                _ => unreachable!(),
+            },
+        }
+    }
+}
+
+/// Spawn every future onto `exec` and wait for all of them to complete.
+///
+/// Every future is spawned at once with [`Executor::try_spawn_all`]. Outputs are pushed into
+/// `results` in the order of `futures`. If spawning a future fails, the tasks that were
+/// already spawned are dropped and the error is returned.
+pub async fn all<E, I, R>(exec: E, futures: I, results: &mut R) -> Result<(), E::Error>
+where
+    I: IntoIterator,
+    I::Item: Future,
+    E: Executor<I::Item>,
+    R: Extend<<I::Item as Future>::Output>,
+{
+    let tasks = exec.try_spawn_all(futures.into_iter().collect())?;
+
+    for task in tasks {
+        results.extend(Some(task.await));
+    }
+
+    Ok(())
+}
+
+/// Like [`all`], but with at most `limit` tasks running at once.
+///
+/// If spawning a future fails, the tasks that were already spawned are dropped and the error
+/// is returned.
+///
+/// # Panics
+///
+/// Panics if `limit` is zero.
+pub async fn all_limited<E, I, R>(
+    exec: E,
+    futures: I,
+    results: &mut R,
+    limit: usize,
+) -> Result<(), E::Error>
+where
+    I: IntoIterator,
+    I::Item: Future,
+    E: Executor<I::Item>,
+    R: Extend<<I::Item as Future>::Output>,
+{
+    assert!(limit > 0, "limit must be greater than zero");
+
+    let mut tasks = OrderedTasks::new(exec);
+    let mut futures = futures.into_iter().fuse();
+
+    poll_fn(|cx| loop {
+        // Spawn futures until the limit is reached.
+        while tasks.len() < limit {
+            match futures.next() {
+                Some(future) => tasks.spawn(future)?,
+                None => break,
+            }
+        }
+
+        match tasks.poll_next(cx) {
+            Poll::Ready(Some(output)) => results.extend(Some(output)),
+            Poll::Ready(None) => return Poll::Ready(Ok(())),
+            Poll::Pending => return Poll::Pending,
+        }
+    })
+    .await
+}
+
+/// An executor that can spawn `F` along with the internal futures the combinators wrap it in.
+///
+/// This is implemented for every [`Executor`] that can spawn any future, so combinators such
+/// as [`or`] only need an `E: AnyExecutor<F>` bound. It cannot be implemented manually.
+pub trait AnyExecutor<F: Future>: Executor<F> {
+    #[doc(hidden)]
+    type RacedTask: CancellableTask<Output = ()>;
+
+    #[doc(hidden)]
+    fn try_spawn_raced(&self, future: sealed::Raced<F>) -> Result<Self::RacedTask, Self::Error>;
+}
+
+impl<F: Future, E> AnyExecutor<F> for E
+where
+    E: Executor<F> + Executor<sealed::Raced<F>, Error = <E as Executor<F>>::Error>,
+    <E as Executor<sealed::Raced<F>>>::Task: CancellableTask,
+{
+    type RacedTask = <E as Executor<sealed::Raced<F>>>::Task;
+
+    #[inline]
+    fn try_spawn_raced(&self, future: sealed::Raced<F>) -> Result<Self::RacedTask, Self::Error> {
+        self.try_spawn(future)
+    }
+}
+
+/// Race every future on `exec` and return the output of the first one to complete.
+///
+/// Once a task completes, every other task is cancelled concurrently.
+///
+/// # Panics
+///
+/// Panics if `futures` is empty, or if the executor drops every task before one completes.
+pub async fn or<E, I, F>(exec: E, futures: I) -> Result<F::Output, E::Error>
+where
+    I: IntoIterator<Item = F>,
+    F: Future,
+    E: AnyExecutor<F>,
+{
+    race(futures, |future| exec.try_spawn_raced(future)).await
+}
+
+/// An [`ExecutorMut`] that can spawn `F` along with the internal futures the combinators wrap
+/// it in.
+///
+/// This is the [`AnyExecutor`] counterpart for combinators such as [`or_mut`]. It cannot be
+/// implemented manually.
+pub trait AnyExecutorMut<F: Future>: ExecutorMut<F> {
+    #[doc(hidden)]
+    type RacedTask: CancellableTask<Output = ()>;
+
+    #[doc(hidden)]
+    fn try_spawn_raced_mut(
+        &mut self,
+        future: sealed::Raced<F>,
+    ) -> Result<Self::RacedTask, Self::Error>;
+}
+
+impl<F: Future, E> AnyExecutorMut<F> for E
+where
+    E: ExecutorMut<F> + ExecutorMut<sealed::Raced<F>, Error = <E as ExecutorMut<F>>::Error>,
+    <E as ExecutorMut<sealed::Raced<F>>>::Task: CancellableTask,
+{
+    type RacedTask = <E as ExecutorMut<sealed::Raced<F>>>::Task;
+
+    #[inline]
+    fn try_spawn_raced_mut(
+        &mut self,
+        future: sealed::Raced<F>,
+    ) -> Result<Self::RacedTask, Self::Error> {
+        self.try_spawn_mut(future)
+    }
+}
+
+/// Like [`or`], but spawns through an [`ExecutorMut`].
+///
+/// # Panics
+///
+/// Panics if `futures` is empty, or if the executor drops every task before one completes.
+pub async fn or_mut<E, I, F>(exec: &mut E, futures: I) -> Result<F::Output, E::Error>
+where
+    I: IntoIterator<Item = F>,
+    F: Future,
+    E: AnyExecutorMut<F>,
+{
+    race(futures, |future| exec.try_spawn_raced_mut(future)).await
+}
+
+/// Spawn every future with `spawn` and return the output of the first one to complete.
+async fn race<I, F, T, Err>(
+    futures: I,
+    mut spawn: impl FnMut(sealed::Raced<F>) -> Result<T, Err>,
+) -> Result<F::Output, Err>
+where
+    I: IntoIterator<Item = F>,
+    F: Future,
+    T: CancellableTask<Output = ()>,
+{
+    let mut futures = futures.into_iter().peekable();
+    assert!(
+        futures.peek().is_some(),
+        "or() requires at least one future"
+    );
+
+    let slot = sealed::OnceSlot::new();
+
+    let tasks = futures
+        .map(|future| spawn(sealed::Raced::new(future, &slot)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let output = poll_fn(|cx| slot.poll_take(cx))
+        .await
+        .expect("the executor dropped every task spawned by or() before one completed");
+
+    // Taking the output closes the slot, so late completions are dropped. The losers are
+    // cancelled together.
+    cancel_all(tasks).await;
+
+    Ok(output)
+}
+
+/// Like [`all`], but spawns through an [`ExecutorMut`].
+pub async fn all_mut<E, I, R>(exec: &mut E, futures: I, results: &mut R) -> Result<(), E::Error>
+where
+    I: IntoIterator,
+    I::Item: Future,
+    E: ExecutorMut<I::Item> + ?Sized,
+    R: Extend<<I::Item as Future>::Output>,
+{
+    all(RefCell::new(exec), futures, results).await
+}
+
+/// Like [`all_limited`], but spawns through an [`ExecutorMut`].
+///
+/// # Panics
+///
+/// Panics if `limit` is zero.
+pub async fn all_limited_mut<E, I, R>(
+    exec: &mut E,
+    futures: I,
+    results: &mut R,
+    limit: usize,
+) -> Result<(), E::Error>
+where
+    I: IntoIterator,
+    I::Item: Future,
+    E: ExecutorMut<I::Item> + ?Sized,
+    R: Extend<<I::Item as Future>::Output>,
+{
+    all_limited(RefCell::new(exec), futures, results, limit).await
+}
+
+/// Like [`all`], for executors that cannot fail to spawn.
+pub async fn all_infallible<E, I, R>(exec: E, futures: I, results: &mut R)
+where
+    I: IntoIterator,
+    I::Item: Future,
+    E: Executor<I::Item, Error = Infallible>,
+    R: Extend<<I::Item as Future>::Output>,
+{
+    all(exec, futures, results).await.unwrap_infallible()
+}
+
+/// Like [`all_limited`], for executors that cannot fail to spawn.
+///
+/// # Panics
+///
+/// Panics if `limit` is zero.
+pub async fn all_limited_infallible<E, I, R>(exec: E, futures: I, results: &mut R, limit: usize)
+where
+    I: IntoIterator,
+    I::Item: Future,
+    E: Executor<I::Item, Error = Infallible>,
+    R: Extend<<I::Item as Future>::Output>,
+{
+    all_limited(exec, futures, results, limit)
+        .await
+        .unwrap_infallible()
+}
+
+/// Like [`or`], for executors that cannot fail to spawn.
+///
+/// # Panics
+///
+/// Panics if `futures` is empty, or if the executor drops every task before one completes.
+pub async fn or_infallible<E, I, F>(exec: E, futures: I) -> F::Output
+where
+    I: IntoIterator<Item = F>,
+    F: Future,
+    E: AnyExecutor<F, Error = Infallible>,
+{
+    or(exec, futures).await.unwrap_infallible()
+}
+
+mod sealed {
+    use alloc::sync::Arc;
+    use core::cell::UnsafeCell;
+    use core::fmt;
+    use core::future::Future;
+    use core::mem::MaybeUninit;
+    use core::pin::Pin;
+    use core::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+    use core::task::{ready, Context, Poll};
+
+    use atomic_waker::AtomicWaker;
+    use pin_project_lite::pin_project;
+
+    const EMPTY: u8 = 0;
+    const WRITING: u8 = 1;
+    const FULL: u8 = 2;
+    const TAKEN: u8 = 3;
+
+    /// The receiving half of a one-shot slot that several [`Raced`] futures compete to fill.
+    pub(super) struct OnceSlot<T>(Arc<Shared<T>>);
+
+    struct Shared<T> {
+        state: AtomicU8,
+        value: UnsafeCell<MaybeUninit<T>>,
+        senders: AtomicUsize,
+        waker: AtomicWaker,
+    }
+
+    // SAFETY: The value is only written by the sender that wins the `EMPTY -> WRITING`
+    // transition and only read by the receiver once the state is `FULL`.
+    unsafe impl<T: Send> Send for Shared<T> {}
+    unsafe impl<T: Send> Sync for Shared<T> {}
+
+    impl<T> OnceSlot<T> {
+        pub(super) fn new() -> Self {
+            Self(Arc::new(Shared {
+                state: AtomicU8::new(EMPTY),
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+                senders: AtomicUsize::new(0),
+                waker: AtomicWaker::new(),
+            }))
+        }
+
+        /// Wait for the first value, or `None` if every sender was dropped without one.
+        pub(super) fn poll_take(&self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+            self.0.waker.register(cx.waker());
+
+            if let Some(value) = self.try_take() {
+                return Poll::Ready(Some(value));
+            }
+
+            // A sender fills the slot before it is dropped, so check once more.
+            if self.0.senders.load(Ordering::Acquire) == 0 {
+                return Poll::Ready(self.try_take());
+            }
+
+            Poll::Pending
+        }
+
+        fn try_take(&self) -> Option<T> {
+            self.0
+                .state
+                .compare_exchange(FULL, TAKEN, Ordering::Acquire, Ordering::Acquire)
+                .ok()?;
+
+            // SAFETY: The state was `FULL`, so the value is initialized, and moving it to
+            // `TAKEN` means it is never read again.
+            Some(unsafe { (*self.0.value.get()).assume_init_read() })
+        }
+    }
+
+    impl<T> Drop for Shared<T> {
+        fn drop(&mut self) {
+            if *self.state.get_mut() == FULL {
+                // SAFETY: The value was written and never taken.
+                unsafe { self.value.get_mut().assume_init_drop() };
+            }
+        }
+    }
+
+    /// The sending half of a [`OnceSlot`].
+    struct Sender<T>(Arc<Shared<T>>);
+
+    impl<T> Sender<T> {
+        fn send(&self, value: T) {
+            let shared = &self.0;
+            if shared
+                .state
+                .compare_exchange(EMPTY, WRITING, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+            {
+                // Another sender won the race, or the receiver is done.
+                return;
+            }
+
+            // SAFETY: Winning the `EMPTY -> WRITING` transition grants exclusive access.
+            unsafe { (*shared.value.get()).write(value) };
+            shared.state.store(FULL, Ordering::Release);
+            shared.waker.wake();
+        }
+    }
+
+    impl<T> Drop for Sender<T> {
+        fn drop(&mut self) {
+            if self.0.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+                self.0.waker.wake();
+            }
+        }
+    }
+
+    pin_project! {
+        /// Future spawned by [`or`](super::or) that hands the output of the inner future to
+        /// the caller.
+        pub struct Raced<F: Future> {
+            #[pin]
+            future: F,
+            sender: Sender<F::Output>,
+        }
+    }
+
+    impl<F: Future> Raced<F> {
+        pub(super) fn new(future: F, slot: &OnceSlot<F::Output>) -> Self {
+            slot.0.senders.fetch_add(1, Ordering::Relaxed);
+            Self {
+                future,
+                sender: Sender(slot.0.clone()),
+            }
+        }
+    }
+
+    impl<F: Future> fmt::Debug for Raced<F> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("Raced { .. }")
+        }
+    }
+
+    impl<F: Future> Future for Raced<F> {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            let output = ready!(this.future.poll(cx));
+
+            // Only the first output is kept; the rest are dropped.
+            this.sender.send(output);
+            Poll::Ready(())
+        }
+    }
+}
+
+/// Spawn every future onto `exec` and yield their outputs in completion order.
+///
+/// If spawning a future fails, the error is yielded and no further futures are spawned. The
+/// tasks that were already spawned still run to completion.
+pub fn all_unordered<E, I>(exec: E, futures: I) -> AllUnordered<E, I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Future,
+    E: Executor<I::Item>,
+{
+    all_unordered_limited(exec, futures, usize::MAX)
+}
+
+/// Like [`all_unordered`], but with at most `limit` tasks running at once.
+///
+/// # Panics
+///
+/// Panics if `limit` is zero.
+pub fn all_unordered_limited<E, I>(
+    exec: E,
+    futures: I,
+    limit: usize,
+) -> AllUnordered<E, I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Future,
+    E: Executor<I::Item>,
+{
+    assert!(limit > 0, "limit must be greater than zero");
+
+    AllUnordered {
+        set: TaskSet::new(exec),
+        futures: Some(futures.into_iter()),
+        limit,
+    }
+}
+
+/// Stream returned by [`all_unordered`] and [`all_unordered_limited`].
+pub struct AllUnordered<E: Executor<I::Item>, I: Iterator>
+where
+    I::Item: Future,
+{
+    set: TaskSet<E, I::Item>,
+    futures: Option<I>,
+    limit: usize,
+}
+
+// The tasks are boxed and the other fields are never pinned.
+impl<E: Executor<I::Item>, I: Iterator> Unpin for AllUnordered<E, I> where I::Item: Future {}
+
+impl<E: Executor<I::Item>, I: Iterator> Stream for AllUnordered<E, I>
+where
+    I::Item: Future,
+{
+    type Item = Result<<I::Item as Future>::Output, E::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        // Spawn futures until the limit is reached.
+        while this.set.len() < this.limit {
+            let future = match this.futures.as_mut().and_then(Iterator::next) {
+                Some(future) => future,
+                None => {
+                    this.futures = None;
+                    break;
+                }
+            };
+
+            if let Err(err) = this.set.spawn(future) {
+                this.futures = None;
+                return Poll::Ready(Some(Err(err)));
+            }
+        }
+
+        this.set.poll_next(cx).map(|output| output.map(Ok))
+    }
+}
+
+/// Spawn every future onto `exec` and yield their outputs in completion order, tagged with
+/// the index of the future that produced them.
+///
+/// If spawning a future fails, the error is yielded and no further futures are spawned. The
+/// tasks that were already spawned still run to completion.
+pub fn all_indexed<E, I>(exec: E, futures: I) -> AllIndexed<E, I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Future,
+    E: Executor<I::Item>,
+{
+    AllIndexed {
+        set: TaskSet::new(exec),
+        futures: Some(futures.into_iter().enumerate()),
+        indices: Vec::new(),
+    }
+}
+
+/// Stream returned by [`all_indexed`].
+pub struct AllIndexed<E: Executor<I::Item>, I: Iterator>
+where
+    I::Item: Future,
+{
+    set: TaskSet<E, I::Item>,
+    futures: Option<core::iter::Enumerate<I>>,
+
+    /// Input indices, indexed by the slot of their task in `set`.
+    indices: Vec<usize>,
+}
+
+impl<E: Executor<I::Item>, I: Iterator> fmt::Debug for AllIndexed<E, I>
+where
+    I::Item: Future,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AllIndexed { .. }")
+    }
+}
+
+// The tasks are boxed and the other fields are never pinned.
+impl<E: Executor<I::Item>, I: Iterator> Unpin for AllIndexed<E, I> where I::Item: Future {}
+
+impl<E: Executor<I::Item>, I: Iterator> Stream for AllIndexed<E, I>
+where
+    I::Item: Future,
+{
+    type Item = Result<(usize, <I::Item as Future>::Output), E::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        // Spawn every remaining future.
+        while let Some((index, future)) = this.futures.as_mut().and_then(Iterator::next) {
+            match this.set.spawn_keyed(future) {
+                Ok(slot) => {
+                    if this.indices.len() <= slot {
+                        this.indices.resize(slot + 1, 0);
+                    }
+                    this.indices[slot] = index;
+                }
+                Err(err) => {
+                    this.futures = None;
+                    return Poll::Ready(Some(Err(err)));
+                }
+            }
+        }
+        this.futures = None;
+
+        this.set
+            .poll_next_keyed(cx)
+            .map(|next| next.map(|(slot, output)| Ok((this.indices[slot], output))))
+    }
+}
+
+/// Spawn every future onto `exec` and yield their outputs in the order of `futures`.
+///
+/// At most `limit` tasks run at once. Outputs that complete out of order are buffered until
+/// every earlier output has been yielded, so memory use is bounded by `limit` rather than by
+/// the number of futures.
+///
+/// If spawning a future fails, the error is yielded after the outputs of the tasks that were
+/// already spawned, and no further futures are spawned.
+///
+/// # Panics
+///
+/// Panics if `limit` is zero.
+pub fn par_map_ordered<E, I>(exec: E, futures: I, limit: usize) -> ParMapOrdered<E, I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Future,
+    E: Executor<I::Item>,
+{
+    assert!(limit > 0, "limit must be greater than zero");
+
+    ParMapOrdered {
+        tasks: OrderedTasks::new(exec),
+        futures: Some(futures.into_iter()),
+        error: None,
+        limit,
+    }
+}
+
+/// Stream returned by [`par_map_ordered`].
+pub struct ParMapOrdered<E: Executor<I::Item>, I: Iterator>
+where
+    I::Item: Future,
+{
+    tasks: OrderedTasks<E, I::Item>,
+    futures: Option<I>,
+    error: Option<E::Error>,
+    limit: usize,
+}
+
+impl<E: Executor<I::Item>, I: Iterator> fmt::Debug for ParMapOrdered<E, I>
+where
+    I::Item: Future,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ParMapOrdered { .. }")
+    }
+}
+
+// The tasks are boxed and the other fields are never pinned.
+impl<E: Executor<I::Item>, I: Iterator> Unpin for ParMapOrdered<E, I> where I::Item: Future {}
+
+impl<E: Executor<I::Item>, I: Iterator> Stream for ParMapOrdered<E, I>
+where
+    I::Item: Future,
+{
+    type Item = Result<<I::Item as Future>::Output, E::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        // Spawn futures until the limit is reached.
+        while this.tasks.len() < this.limit {
+            let future = match this.futures.as_mut().and_then(Iterator::next) {
+                Some(future) => future,
+                None => {
+                    this.futures = None;
+                    break;
+                }
+            };
+
+            if let Err(err) = this.tasks.spawn(future) {
+                this.futures = None;
+                this.error = Some(err);
+                break;
+            }
+        }
+
+        match this.tasks.poll_next(cx) {
+            Poll::Ready(Some(output)) => Poll::Ready(Some(Ok(output))),
+            Poll::Ready(None) => Poll::Ready(this.error.take().map(Err)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Start a multi-stage pipeline that feeds `items` through a series of concurrent stages.
+///
+/// Each stage is added with [`Pipeline::stage`] and spawns its futures onto its own executor
+/// with its own concurrency limit. A stage only pulls items from the stage before it while it
+/// has room, so every stage buffers at most `limit` items. Outputs are yielded in input order.
+///
+/// `Err` is the error type of the whole pipeline; every stage's spawn error must convert into
+/// it. After the first error, no further items are pulled, and the error is yielded once the
+/// items already in flight have been yielded.
+pub fn pipeline<I: IntoIterator, Err>(items: I) -> Pipeline<Source<I::IntoIter, Err>> {
+    Pipeline {
+        stream: Source {
+            items: items.into_iter(),
+            _error: PhantomData,
+        },
+    }
+}
+
+/// A multi-stage concurrent pipeline.
+///
+/// Created by [`pipeline`]. This is a [`Stream`] of the outputs of the last stage.
+#[derive(Debug)]
+pub struct Pipeline<S> {
+    stream: S,
+}
+
+impl<S> Pipeline<S> {
+    /// Add a stage that spawns `f(item)` onto `exec` for every item, with at most `limit`
+    /// tasks running at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is zero.
+    pub fn stage<E, M, Fut, T, Err>(
+        self,
+        exec: E,
+        limit: usize,
+        f: M,
+    ) -> Pipeline<Stage<S, E, M, Fut, Err>>
+    where
+        S: Stream<Item = Result<T, Err>>,
+        M: FnMut(T) -> Fut,
+        Fut: Future,
+        E: Executor<Fut>,
+        E::Error: Into<Err>,
+    {
+        assert!(limit > 0, "limit must be greater than zero");
+
+        Pipeline {
+            stream: Stage {
+                tasks: OrderedTasks::new(exec),
+                upstream: Some(Box::pin(self.stream)),
+                error: None,
+                f,
+                limit,
+            },
+        }
+    }
+
+    /// Convert into the stream of outputs of the last stage.
+    pub fn into_stream(self) -> S {
+        self.stream
+    }
+}
+
+impl<S: Stream + Unpin> Stream for Pipeline<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.get_mut().stream).poll_next(cx)
+    }
+}
+
+/// The input stage of a [`Pipeline`].
+pub struct Source<I, Err> {
+    items: I,
+    _error: PhantomData<fn() -> Err>,
+}
+
+impl<I: fmt::Debug, Err> fmt::Debug for Source<I, Err> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Source")
+            .field("items", &self.items)
+            .finish()
+    }
+}
+
+impl<I, Err> Unpin for Source<I, Err> {}
+
+impl<I: Iterator, Err> Stream for Source<I, Err> {
+    type Item = Result<I::Item, Err>;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.get_mut().items.next().map(Ok))
+    }
+}
+
+/// A concurrent stage of a [`Pipeline`].
+pub struct Stage<S, E: Executor<Fut>, M, Fut: Future, Err> {
+    tasks: OrderedTasks<E, Fut>,
+    upstream: Option<Pin<Box<S>>>,
+    error: Option<Err>,
+    f: M,
+    limit: usize,
+}
+
+impl<S, E: Executor<Fut>, M, Fut: Future, Err> fmt::Debug for Stage<S, E, M, Fut, Err> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Stage { .. }")
+    }
+}
+
+// The upstream and tasks are boxed and the other fields are never pinned.
+impl<S, E: Executor<Fut>, M, Fut: Future, Err> Unpin for Stage<S, E, M, Fut, Err> {}
+
+impl<S, E, M, Fut, T, Err> Stream for Stage<S, E, M, Fut, Err>
+where
+    S: Stream<Item = Result<T, Err>>,
+    M: FnMut(T) -> Fut,
+    Fut: Future,
+    E: Executor<Fut>,
+    E::Error: Into<Err>,
+{
+    type Item = Result<Fut::Output, Err>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        // Pull items from upstream until the limit is reached.
+        while this.tasks.len() < this.limit {
+            let upstream = match &mut this.upstream {
+                Some(upstream) => upstream,
+                None => break,
+            };
+
+            match upstream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(item))) => {
+                    if let Err(err) = this.tasks.spawn((this.f)(item)) {
+                        this.upstream = None;
+                        this.error = Some(err.into());
+                    }
+                }
+                Poll::Ready(Some(Err(err))) => {
+                    this.upstream = None;
+                    this.error = Some(err);
+                }
+                Poll::Ready(None) => this.upstream = None,
+                Poll::Pending => break,
+            }
+        }
+
+        match this.tasks.poll_next(cx) {
+            Poll::Ready(Some(output)) => Poll::Ready(Some(Ok(output))),
+            Poll::Ready(None) if this.upstream.is_none() => Poll::Ready(this.error.take().map(Err)),
+            _ => Poll::Pending,
+        }
+    }
+}
+
+/// Spawn every future onto `exec`, weighted by cost, and wait for all of them to complete.
+///
+/// Each future is paired with a weight, and futures are only spawned while the total weight
+/// of the running tasks stays within `capacity`. A future that is heavier than `capacity` on
+/// its own is spawned once nothing else is running. Outputs are pushed into `results` in
+/// input order.
+///
+/// If spawning a future fails, every running task is cancelled and the error is returned.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+pub async fn all_weighted<E, I, F, R>(
+    exec: E,
+    futures: I,
+    results: &mut R,
+    capacity: usize,
+) -> Result<(), E::Error>
+where
+    I: IntoIterator<Item = (F, usize)>,
+    F: Future,
+    E: Executor<F>,
+    R: Extend<F::Output>,
+{
+    assert!(capacity > 0, "capacity must be greater than zero");
+
+    let mut futures = futures.into_iter();
+    let mut next = futures.next();
+    let mut running = Vec::new();
+    let mut used = 0usize;
+
+    // Outputs that cannot be pushed yet, starting at input index `flushed`.
+    let mut pending = VecDeque::new();
+    let mut flushed = 0;
+
+    poll_fn(|cx| loop {
+        // Spawn futures until the next one no longer fits.
+        while let Some((future, weight)) = next.take() {
+            if !running.is_empty() && used.saturating_add(weight) > capacity {
+                next = Some((future, weight));
+                break;
+            }
+
+            let task = exec.try_spawn(future)?;
+            running.push((flushed + pending.len(), weight, Box::pin(task)));
+            pending.push_back(None);
+            used += weight;
+            next = futures.next();
+        }
+
+        if running.is_empty() {
+            return Poll::Ready(Ok(()));
+        }
+
+        let before = running.len();
+        running.retain_mut(|(index, weight, task)| match task.as_mut().poll(cx) {
+            Poll::Ready(output) => {
+                pending[*index - flushed] = Some(output);
+                used -= *weight;
+                false
+            }
+            Poll::Pending => true,
+        });
+
+        while let Some(Some(_)) = pending.front() {
+            results.extend(pending.pop_front().flatten());
+            flushed += 1;
+        }
+
+        // Only wait if no capacity was freed up.
+        if running.len() == before {
+            return Poll::Pending;
+        }
+    })
+    .await
+}
+
+/// Spawn every fallible future onto `exec` and wait for all of them to succeed.
+///
+/// Outputs are returned in spawn order. As soon as any task fails, every other task is
+/// cancelled by dropping it and its error is returned. Tasks are pinned in place, so they do
+/// not need to be [`Unpin`].
+pub async fn try_all<E, I, T, Err>(exec: E, futures: I) -> Result<Result<Vec<T>, Err>, E::Error>
+where
+    I: IntoIterator,
+    I::Item: Future<Output = Result<T, Err>>,
+    E: Executor<I::Item>,
+{
+    let mut tasks = exec
+        .try_spawn_all(futures.into_iter().collect())?
+        .into_iter()
+        .map(|task| Some(Box::pin(task)))
+        .collect::<Vec<_>>();
+    let mut results = tasks.iter().map(|_| None).collect::<Vec<_>>();
+
+    let failure = poll_fn(|cx| {
+        let mut pending = false;
+
+        for (task, result) in tasks.iter_mut().zip(results.iter_mut()) {
+            if let Some(inner) = task {
+                match inner.as_mut().poll(cx) {
+                    Poll::Pending => pending = true,
+                    Poll::Ready(Ok(output)) => {
+                        *task = None;
+                        *result = Some(output);
+                    }
+                    Poll::Ready(Err(err)) => {
+                        *task = None;
+                        return Poll::Ready(Some(err));
+                    }
+                }
+            }
+        }
+
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(None)
+        }
+    })
+    .await;
+
+    match failure {
+        Some(err) => {
+            drop(tasks);
+            Ok(Err(err))
+        }
+        None => Ok(Ok(results.into_iter().flatten().collect())),
+    }
+}
+
+/// Race fallible futures on `exec` and return the first successful output.
+///
+/// Once a task succeeds, every other task is cancelled by dropping it. If every task fails,
+/// their errors are returned in spawn order. Tasks are pinned in place, so they do not need to
+/// be [`Unpin`].
+pub async fn race_ok<E, I, T, Err>(exec: E, futures: I) -> Result<Result<T, Vec<Err>>, E::Error>
+where
+    I: IntoIterator,
+    I::Item: Future<Output = Result<T, Err>>,
+    E: Executor<I::Item>,
+{
+    let mut tasks = exec
+        .try_spawn_all(futures.into_iter().collect())?
+        .into_iter()
+        .map(|task| Some(Box::pin(task)))
+        .collect::<Vec<_>>();
+    let mut errors = tasks.iter().map(|_| None).collect::<Vec<_>>();
+
+    let success = poll_fn(|cx| {
+        let mut pending = false;
+
+        for (task, error) in tasks.iter_mut().zip(errors.iter_mut()) {
+            if let Some(inner) = task {
+                match inner.as_mut().poll(cx) {
+                    Poll::Pending => pending = true,
+                    Poll::Ready(Ok(output)) => {
+                        *task = None;
+                        return Poll::Ready(Some(output));
+                    }
+                    Poll::Ready(Err(err)) => {
+                        *task = None;
+                        *error = Some(err);
+                    }
+                }
+            }
+        }
+
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(None)
+        }
+    })
+    .await;
+
+    match success {
+        Some(output) => {
+            drop(tasks);
+            Ok(Ok(output))
+        }
+        None => Ok(Err(errors.into_iter().flatten().collect())),
+    }
+}
+
+/// How [`spawn_retry`] retries failed attempts.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct RetryPolicy<T> {
+    timer: T,
+    max_retries: usize,
+    delay: core::time::Duration,
+    factor: u32,
+}
+
+#[cfg(feature = "std")]
+impl<T: crate::Timer> RetryPolicy<T> {
+    /// Create a new policy that retries up to three times without waiting, using `timer` for
+    /// any delays.
+    pub fn new(timer: T) -> Self {
+        Self {
+            timer,
+            max_retries: 3,
+            delay: core::time::Duration::ZERO,
+            factor: 1,
+        }
+    }
+
+    /// Set the number of times a failed attempt is retried.
+    pub fn max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the delay before the first retry.
+    pub fn delay(mut self, delay: core::time::Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Multiply the delay by `factor` after every retry.
+    pub fn backoff_factor(mut self, factor: u32) -> Self {
+        self.factor = factor;
+        self
+    }
+}
+
+/// Spawn attempts created by `make_future` onto `exec` until one succeeds.
+///
+/// Failed attempts are retried according to `policy`. If every attempt fails, the error of the
+/// last one is returned.
+#[cfg(feature = "std")]
+pub async fn spawn_retry<E, M, F, T, Err, Ti>(
+    exec: E,
+    mut make_future: M,
+    policy: RetryPolicy<Ti>,
+) -> Result<Result<T, Err>, E::Error>
+where
+    M: FnMut() -> F,
+    F: Future<Output = Result<T, Err>>,
+    E: Executor<F>,
+    Ti: crate::Timer,
+{
+    let mut delay = policy.delay;
+    let mut retries = 0;
+
+    loop {
+        match exec.try_spawn(make_future())?.await {
+            Ok(output) => return Ok(Ok(output)),
+            Err(err) if retries >= policy.max_retries => return Ok(Err(err)),
+            Err(_) => {}
+        }
+
+        if !delay.is_zero() {
+            policy.timer.sleep(delay).await;
+        }
+
+        delay = delay.saturating_mul(policy.factor);
+        retries += 1;
+    }
+}
+
+/// Cancel every task concurrently.
+async fn cancel_all<T: CancellableTask>(tasks: impl IntoIterator<Item = T>) {
+    let mut cancels = tasks
+        .into_iter()
+        .map(|task| Some(Box::pin(task.cancel())))
+        .collect::<Vec<_>>();
+
+    poll_fn(|cx| {
+        let mut pending = false;
+
+        for cancel in cancels.iter_mut() {
+            if let Some(inner) = cancel {
+                match inner.as_mut().poll(cx) {
+                    Poll::Pending => pending = true,
+                    Poll::Ready(_) => *cancel = None,
+                }
+            }
+        }
+
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    })
+    .await
+}
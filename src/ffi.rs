@@ -0,0 +1,85 @@
+//! Executors provided by a C host application.
+
+use crate::Executor;
+use async_task_crate::{Runnable, Task};
+
+use core::convert::Infallible;
+use core::ffi::c_void;
+use core::future::Future;
+use core::ptr::NonNull;
+
+/// Callback that the host invokes to run a scheduled task.
+pub type HostCallback = unsafe extern "C" fn(task: *mut c_void);
+
+/// Function provided by the host to schedule a callback on its event loop.
+///
+/// The host must eventually call `callback(task)` exactly once.
+pub type HostScheduleFn =
+    unsafe extern "C" fn(ctx: *mut c_void, callback: HostCallback, task: *mut c_void);
+
+/// An [`Executor`] that schedules tasks onto a host event loop through C function pointers.
+///
+/// Wakers and task completion are handled by this crate; the host only needs to run the
+/// callbacks it is given.
+#[derive(Debug, Clone, Copy)]
+pub struct HostExecutor {
+    schedule: HostScheduleFn,
+    ctx: *mut c_void,
+}
+
+// SAFETY: The contract of `HostExecutor::new` requires `schedule` to be callable with `ctx`
+// from any thread.
+unsafe impl Send for HostExecutor {}
+unsafe impl Sync for HostExecutor {}
+
+impl HostExecutor {
+    /// Create a new `HostExecutor` from a scheduling function and its context.
+    ///
+    /// # Safety
+    ///
+    /// `schedule` must be safe to call with `ctx` from any thread for as long as this executor
+    /// or any task spawned onto it is alive. Every callback passed to `schedule` must eventually
+    /// be invoked exactly once with the task pointer it was given.
+    pub unsafe fn new(schedule: HostScheduleFn, ctx: *mut c_void) -> Self {
+        Self { schedule, ctx }
+    }
+
+    /// Get the context pointer passed to the host.
+    pub fn context(&self) -> *mut c_void {
+        self.ctx
+    }
+
+    fn schedule(&self, runnable: Runnable) {
+        let task = runnable.into_raw().as_ptr().cast::<c_void>();
+
+        // SAFETY: Upheld by the contract of `HostExecutor::new`.
+        unsafe { (self.schedule)(self.ctx, run_task, task) }
+    }
+}
+
+/// Run a task that was handed to the host.
+///
+/// Tasks are spawned with `propagate_panic`, so a panicking future does not unwind into the
+/// host. The panic resumes wherever the task's handle is awaited instead.
+unsafe extern "C" fn run_task(task: *mut c_void) {
+    // SAFETY: The host passes back the pointer produced by `Runnable::into_raw` exactly once.
+    let runnable = unsafe { Runnable::<()>::from_raw(NonNull::new_unchecked(task.cast())) };
+    runnable.run();
+}
+
+impl<F: Future + Send + 'static> Executor<F> for HostExecutor
+where
+    F::Output: Send + 'static,
+{
+    type Task = Task<F::Output>;
+    type Error = Infallible;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        let this = *self;
+        let (runnable, task) = async_task_crate::Builder::new()
+            .propagate_panic(true)
+            .spawn(|()| future, move |runnable| this.schedule(runnable));
+        runnable.schedule();
+        Ok(task)
+    }
+}
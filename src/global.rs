@@ -0,0 +1,91 @@
+//! A process-wide default executor.
+//!
+//! Applications install their runtime once with [`set_global_executor`], after which libraries
+//! can [`spawn`] onto it without taking an executor parameter.
+
+use crate::{DynExecutor, DynTask, Executor, SpawnError};
+
+use alloc::boxed::Box;
+
+use core::fmt;
+use core::future::Future;
+
+use std::sync::OnceLock;
+
+static GLOBAL: OnceLock<Box<dyn DynExecutor<'static> + Send + Sync>> = OnceLock::new();
+
+/// Install the global executor.
+///
+/// Fails if a global executor has already been installed.
+pub fn set_global_executor<E: DynExecutor<'static> + Send + Sync + 'static>(
+    exec: E,
+) -> Result<(), SetGlobalError> {
+    GLOBAL
+        .set(Box::new(exec))
+        .map_err(|_| SetGlobalError { _private: () })
+}
+
+/// Get the global executor, if one has been installed.
+pub fn global_executor() -> Option<&'static (dyn DynExecutor<'static> + Send + Sync)> {
+    GLOBAL.get().map(|exec| &**exec)
+}
+
+/// Spawn a future onto the global executor.
+///
+/// Fails with [`NoGlobalExecutor`] if no global executor has been installed.
+pub fn spawn<F: Future + Send + 'static>(
+    future: F,
+) -> Result<DynTask<'static, F::Output>, SpawnError>
+where
+    F::Output: Send + 'static,
+{
+    Global::default().try_spawn(future)
+}
+
+/// An [`Executor`] that spawns onto the global executor.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Global {
+    _private: (),
+}
+
+impl<F: Future + Send + 'static> Executor<F> for Global
+where
+    F::Output: Send + 'static,
+{
+    type Task = DynTask<'static, F::Output>;
+    type Error = SpawnError;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        let exec: &dyn DynExecutor<'static> =
+            global_executor().ok_or_else(|| SpawnError::new(NoGlobalExecutor { _private: () }))?;
+        exec.try_spawn(future)
+    }
+}
+
+/// Error returned by [`set_global_executor`] if a global executor is already installed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetGlobalError {
+    _private: (),
+}
+
+impl fmt::Display for SetGlobalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a global executor has already been installed")
+    }
+}
+
+impl std::error::Error for SetGlobalError {}
+
+/// Error returned when spawning before a global executor has been installed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoGlobalExecutor {
+    _private: (),
+}
+
+impl fmt::Display for NoGlobalExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("no global executor has been installed")
+    }
+}
+
+impl std::error::Error for NoGlobalExecutor {}
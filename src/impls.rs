@@ -2,7 +2,7 @@
 
 #[cfg(feature = "async-task")]
 mod async_task_impl {
-    use crate::{CancellableTask, DetachableTask};
+    use crate::{CancellableTask, DetachableTask, InspectableTask};
     use async_task_crate::{FallibleTask, Task};
 
     use core::future::Future;
@@ -10,24 +10,49 @@ mod async_task_impl {
 
     use alloc::boxed::Box;
 
-    impl<'a, T: Send + 'a, M: Send + Sync + 'a> CancellableTask<'a> for Task<T, M> {
-        type Cancel = Pin<Box<dyn Future<Output = Option<T>> + Send + 'a>>;
+    impl<T: Send, M: Send + Sync> CancellableTask for Task<T, M> {
+        type Cancel<'a>
+            = Pin<Box<dyn Future<Output = Option<T>> + Send + 'a>>
+        where
+            Self: 'a;
 
-        fn cancel(self) -> Self::Cancel {
+        fn cancel<'a>(self) -> Self::Cancel<'a>
+        where
+            Self: 'a,
+        {
             Box::pin(Task::cancel(self))
         }
     }
 
+    #[cfg(feature = "afit")]
+    impl<T, M> crate::AsyncCancellableTask for Task<T, M> {
+        async fn cancel(self) -> Option<T> {
+            Task::cancel(self).await
+        }
+    }
+
     impl<T, M> DetachableTask for Task<T, M> {
         fn detach(self) {
             Task::detach(self)
         }
     }
 
-    impl<'a, T: Send + 'a, M: Send + Sync + 'a> CancellableTask<'a> for FallibleTask<T, M> {
-        type Cancel = Pin<Box<dyn Future<Output = Option<Option<T>>> + Send + 'a>>;
+    impl<T, M> InspectableTask for Task<T, M> {
+        fn is_finished(&self) -> bool {
+            Task::is_finished(self)
+        }
+    }
+
+    impl<T: Send, M: Send + Sync> CancellableTask for FallibleTask<T, M> {
+        type Cancel<'a>
+            = Pin<Box<dyn Future<Output = Option<Option<T>>> + Send + 'a>>
+        where
+            Self: 'a;
 
-        fn cancel(self) -> Self::Cancel {
+        fn cancel<'a>(self) -> Self::Cancel<'a>
+        where
+            Self: 'a,
+        {
             Box::pin(async move {
                 let result = FallibleTask::cancel(self).await;
                 Some(result)
@@ -35,11 +60,24 @@ mod async_task_impl {
         }
     }
 
+    #[cfg(feature = "afit")]
+    impl<T, M> crate::AsyncCancellableTask for FallibleTask<T, M> {
+        async fn cancel(self) -> Option<Option<T>> {
+            Some(FallibleTask::cancel(self).await)
+        }
+    }
+
     impl<T, M> DetachableTask for FallibleTask<T, M> {
         fn detach(self) {
             FallibleTask::detach(self)
         }
     }
+
+    impl<T, M> InspectableTask for FallibleTask<T, M> {
+        fn is_finished(&self) -> bool {
+            FallibleTask::is_finished(self)
+        }
+    }
 }
 
 #[cfg(feature = "async-executor")]
@@ -47,6 +85,8 @@ mod async_executor_impl {
     use crate::Executor;
     use async_executor_crate::{LocalExecutor, Task};
 
+    use alloc::vec::Vec;
+
     use core::convert::Infallible;
     use core::future::Future;
 
@@ -60,6 +100,13 @@ mod async_executor_impl {
         fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
             Ok(self.spawn(future))
         }
+
+        /// Spawns the whole batch under a single lock with `spawn_many`.
+        fn try_spawn_all(&self, futures: Vec<F>) -> Result<Vec<Self::Task>, Self::Error> {
+            let mut tasks = Vec::with_capacity(futures.len());
+            self.spawn_many(futures, &mut tasks);
+            Ok(tasks)
+        }
     }
 
     impl<'a, F: Future + 'a> Executor<F> for LocalExecutor<'a>
@@ -72,17 +119,38 @@ mod async_executor_impl {
         fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
             Ok(self.spawn(future))
         }
+
+        /// Spawns the whole batch under a single lock with `spawn_many`.
+        fn try_spawn_all(&self, futures: Vec<F>) -> Result<Vec<Self::Task>, Self::Error> {
+            let mut tasks = Vec::with_capacity(futures.len());
+            self.spawn_many(futures, &mut tasks);
+            Ok(tasks)
+        }
+    }
+
+    impl<'a, F: Future + 'a> crate::LocalExecutor<F> for LocalExecutor<'a>
+    where
+        F::Output: 'a,
+    {
+        type Task = Task<F::Output>;
+        type Error = Infallible;
+
+        fn try_spawn_local(&self, future: F) -> Result<Self::Task, Self::Error> {
+            Ok(self.spawn(future))
+        }
     }
 }
 
 #[cfg(feature = "tokio")]
 mod tokio_impl {
-    use crate::{CancellableTask, DetachableTask, Executor};
+    use crate::{
+        BlockingExecutor, CancellableTask, DetachableTask, Executor, InspectableTask, LocalExecutor,
+    };
     use tokio::runtime::{Handle, Runtime};
-    use tokio::task::JoinHandle;
+    use tokio::task::{JoinError, JoinHandle, LocalSet};
 
     use core::convert::Infallible;
-    use core::future::{ready, Future, Ready};
+    use core::future::Future;
     use core::pin::Pin;
     use core::task::{Context, Poll};
 
@@ -125,6 +193,12 @@ mod tokio_impl {
         pub fn into_inner(mut self) -> JoinHandle<T> {
             self.0.take().unwrap()
         }
+
+        /// Convert into a task that outputs an error instead of panicking if the task was
+        /// cancelled or panicked.
+        pub fn fallible(mut self) -> FallibleTokioTask<T> {
+            FallibleTokioTask(self.0.take())
+        }
     }
 
     impl<T> Future for TokioTask<T> {
@@ -145,14 +219,39 @@ mod tokio_impl {
         }
     }
 
-    impl<'a, T: 'a> CancellableTask<'a> for TokioTask<T> {
-        type Cancel = Ready<Option<T>>;
+    impl<T> CancellableTask for TokioTask<T> {
+        type Cancel<'a>
+            = TokioCancel<T>
+        where
+            T: 'a;
+
+        fn cancel<'a>(mut self) -> Self::Cancel<'a>
+        where
+            T: 'a,
+        {
+            let handle = self.0.take().unwrap();
+            handle.abort();
+            TokioCancel(handle)
+        }
+    }
 
-        fn cancel(mut self) -> Self::Cancel {
-            self.get_mut().abort();
+    #[cfg(feature = "afit")]
+    impl<T> crate::AsyncCancellableTask for TokioTask<T> {
+        async fn cancel(self) -> Option<T> {
+            CancellableTask::cancel(self).await
+        }
+    }
+
+    /// Future returned by cancelling a [`TokioTask`].
+    ///
+    /// Resolves to the output of the task if it completed before it could be aborted.
+    pub struct TokioCancel<T>(JoinHandle<T>);
 
-            // TODO: Poll self once noop_waker is stable.
-            ready(None)
+    impl<T> Future for TokioCancel<T> {
+        type Output = Option<T>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            Pin::new(&mut self.0).poll(cx).map(Result::ok)
         }
     }
 
@@ -163,6 +262,16 @@ mod tokio_impl {
         }
     }
 
+    impl<T> InspectableTask for TokioTask<T> {
+        fn is_finished(&self) -> bool {
+            // The handle is only taken once the task has completed.
+            match &self.0 {
+                Some(handle) => handle.is_finished(),
+                None => true,
+            }
+        }
+    }
+
     impl<T> Drop for TokioTask<T> {
         fn drop(&mut self) {
             if let Some(task) = self.0.take() {
@@ -171,6 +280,106 @@ mod tokio_impl {
         }
     }
 
+    /// A wrapper around a [`tokio::task::JoinHandle`] that outputs a [`JoinError`] on failure.
+    ///
+    /// Created by [`TokioTask::fallible`].
+    pub struct FallibleTokioTask<T>(Option<JoinHandle<T>>);
+
+    impl<T> FallibleTokioTask<T> {
+        /// Get a reference to the inner `JoinHandle`.
+        pub fn get_ref(&self) -> &JoinHandle<T> {
+            self.0.as_ref().unwrap()
+        }
+
+        /// Get a mutable reference to the inner `JoinHandle`.
+        pub fn get_mut(&mut self) -> &mut JoinHandle<T> {
+            self.0.as_mut().unwrap()
+        }
+
+        /// Convert to the inner join handle.
+        pub fn into_inner(mut self) -> JoinHandle<T> {
+            self.0.take().unwrap()
+        }
+    }
+
+    impl<T> Future for FallibleTokioTask<T> {
+        type Output = Result<T, JoinError>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            match Pin::new(self.0.as_mut().unwrap()).poll(cx) {
+                Poll::Pending => Poll::Pending,
+                Poll::Ready(result) => {
+                    self.0 = None;
+                    Poll::Ready(result)
+                }
+            }
+        }
+    }
+
+    impl<T> CancellableTask for FallibleTokioTask<T> {
+        type Cancel<'a>
+            = FallibleTokioCancel<T>
+        where
+            T: 'a;
+
+        fn cancel<'a>(mut self) -> Self::Cancel<'a>
+        where
+            T: 'a,
+        {
+            let handle = self.0.take().unwrap();
+            handle.abort();
+            FallibleTokioCancel(handle)
+        }
+    }
+
+    #[cfg(feature = "afit")]
+    impl<T> crate::AsyncCancellableTask for FallibleTokioTask<T> {
+        async fn cancel(self) -> Option<Result<T, JoinError>> {
+            CancellableTask::cancel(self).await
+        }
+    }
+
+    /// Future returned by cancelling a [`FallibleTokioTask`].
+    ///
+    /// Resolves to the result of the task unless it was aborted before completing.
+    pub struct FallibleTokioCancel<T>(JoinHandle<T>);
+
+    impl<T> Future for FallibleTokioCancel<T> {
+        type Output = Option<Result<T, JoinError>>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            Pin::new(&mut self.0).poll(cx).map(|result| match result {
+                Err(err) if err.is_cancelled() => None,
+                result => Some(result),
+            })
+        }
+    }
+
+    impl<T> DetachableTask for FallibleTokioTask<T> {
+        fn detach(mut self) {
+            // Dropping the tokio task automatically detaches it.
+            self.0 = None;
+        }
+    }
+
+    impl<T> InspectableTask for FallibleTokioTask<T> {
+        fn is_finished(&self) -> bool {
+            // The handle is only taken once the task has completed.
+            match &self.0 {
+                Some(handle) => handle.is_finished(),
+                None => true,
+            }
+        }
+    }
+
+    impl<T> Drop for FallibleTokioTask<T> {
+        fn drop(&mut self) {
+            if let Some(task) = self.0.take() {
+                task.abort();
+            }
+        }
+    }
+
     impl<F: Future + Send + 'static> Executor<F> for Handle
     where
         F::Output: Send + 'static,
@@ -194,7 +403,555 @@ mod tokio_impl {
             Ok(TokioTask(Some(self.spawn(future))))
         }
     }
+
+    impl<F: Future + 'static> Executor<F> for LocalSet
+    where
+        F::Output: 'static,
+    {
+        type Error = Infallible;
+        type Task = TokioTask<F::Output>;
+
+        fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+            Ok(TokioTask(Some(self.spawn_local(future))))
+        }
+    }
+
+    impl<F: Future + 'static> LocalExecutor<F> for LocalSet
+    where
+        F::Output: 'static,
+    {
+        type Error = Infallible;
+        type Task = TokioTask<F::Output>;
+
+        fn try_spawn_local(&self, future: F) -> Result<Self::Task, Self::Error> {
+            Ok(TokioTask(Some(self.spawn_local(future))))
+        }
+    }
+
+    impl<F: FnOnce() -> T + Send + 'static, T: Send + 'static> BlockingExecutor<F, T> for TokioGlobal {
+        type Task = TokioTask<T>;
+        type Error = tokio::runtime::TryCurrentError;
+
+        fn try_spawn_blocking(&self, f: F) -> Result<Self::Task, Self::Error> {
+            Handle::try_current().map(|handle| TokioTask(Some(handle.spawn_blocking(f))))
+        }
+    }
+
+    impl<F: FnOnce() -> T + Send + 'static, T: Send + 'static> BlockingExecutor<F, T> for Handle {
+        type Task = TokioTask<T>;
+        type Error = Infallible;
+
+        fn try_spawn_blocking(&self, f: F) -> Result<Self::Task, Self::Error> {
+            Ok(TokioTask(Some(self.spawn_blocking(f))))
+        }
+    }
+
+    impl<F: FnOnce() -> T + Send + 'static, T: Send + 'static> BlockingExecutor<F, T> for Runtime {
+        type Task = TokioTask<T>;
+        type Error = Infallible;
+
+        fn try_spawn_blocking(&self, f: F) -> Result<Self::Task, Self::Error> {
+            Ok(TokioTask(Some(self.spawn_blocking(f))))
+        }
+    }
+
+    #[cfg(feature = "tokio-util")]
+    impl<F: Future + Send + 'static> Executor<F> for tokio_util_crate::task::LocalPoolHandle
+    where
+        F::Output: Send + 'static,
+    {
+        type Error = Infallible;
+        type Task = TokioTask<F::Output>;
+
+        fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+            Ok(TokioTask(Some(self.spawn_pinned(move || future))))
+        }
+    }
+
+    /// Implements [`Timer`](crate::Timer) using `tokio`'s timer.
+    ///
+    /// Sleeps must be started from within a runtime that has the time driver enabled. This
+    /// requires the `timer` feature.
+    #[cfg(feature = "timer")]
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct TokioTimer {
+        _private: (),
+    }
+
+    #[cfg(feature = "timer")]
+    impl crate::Timer for TokioTimer {
+        type Sleep = tokio::time::Sleep;
+
+        fn sleep_until(&self, deadline: std::time::Instant) -> Self::Sleep {
+            tokio::time::sleep_until(deadline.into())
+        }
+
+        fn sleep(&self, duration: core::time::Duration) -> Self::Sleep {
+            tokio::time::sleep(duration)
+        }
+    }
 }
 
+#[cfg(feature = "async-io")]
+mod async_io_impl {
+    use crate::Timer;
+
+    use core::future::Future;
+    use core::pin::Pin;
+    use core::task::{Context, Poll};
+    use core::time::Duration;
+
+    use std::time::Instant;
+
+    /// Implements [`Timer`] using `async-io`'s timers.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct AsyncIoTimer {
+        _private: (),
+    }
+
+    impl Timer for AsyncIoTimer {
+        type Sleep = AsyncIoSleep;
+
+        fn sleep_until(&self, deadline: Instant) -> Self::Sleep {
+            AsyncIoSleep(async_io_crate::Timer::at(deadline))
+        }
+
+        fn sleep(&self, duration: Duration) -> Self::Sleep {
+            AsyncIoSleep(async_io_crate::Timer::after(duration))
+        }
+    }
+
+    /// Future returned by sleeping on an [`AsyncIoTimer`].
+    #[derive(Debug)]
+    pub struct AsyncIoSleep(async_io_crate::Timer);
+
+    impl Future for AsyncIoSleep {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            Pin::new(&mut self.0).poll(cx).map(|_| ())
+        }
+    }
+}
+
+#[cfg(feature = "async-io")]
+pub use async_io_impl::{AsyncIoSleep, AsyncIoTimer};
+
+#[cfg(feature = "async-std")]
+mod async_std_impl {
+    use crate::{CancellableTask, DetachableTask, Executor, MetadataExecutor};
+    use async_std_crate::task::{self, JoinHandle};
+
+    use core::future::Future;
+    use core::pin::Pin;
+    use core::task::{Context, Poll};
+
+    use alloc::boxed::Box;
+
+    use std::io;
+
+    /// Implements traits for `async-std`'s global runtime.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct AsyncStdGlobal {
+        _private: (),
+    }
+
+    impl<F: Future + Send + 'static> Executor<F> for AsyncStdGlobal
+    where
+        F::Output: Send + 'static,
+    {
+        type Task = AsyncStdTask<F::Output>;
+        type Error = io::Error;
+
+        fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+            let handle = task::Builder::new().spawn(future)?;
+            Ok(AsyncStdTask(Some(handle)))
+        }
+    }
+
+    /// Names the spawned task.
+    impl<F: Future + Send + 'static> MetadataExecutor<F, &str> for AsyncStdGlobal
+    where
+        F::Output: Send + 'static,
+    {
+        fn try_spawn_with(&self, future: F, name: &str) -> Result<Self::Task, Self::Error> {
+            let handle = task::Builder::new().name(name.into()).spawn(future)?;
+            Ok(AsyncStdTask(Some(handle)))
+        }
+    }
+
+    /// A wrapper around an [`async_std::task::JoinHandle`] that is cancelled on drop.
+    ///
+    /// [`async_std::task::JoinHandle`]: async_std_crate::task::JoinHandle
+    pub struct AsyncStdTask<T: Send + 'static>(Option<JoinHandle<T>>);
+
+    impl<T: Send + 'static> AsyncStdTask<T> {
+        /// Get a reference to the inner `JoinHandle`.
+        pub fn get_ref(&self) -> &JoinHandle<T> {
+            self.0.as_ref().unwrap()
+        }
+
+        /// Get a mutable reference to the inner `JoinHandle`.
+        pub fn get_mut(&mut self) -> &mut JoinHandle<T> {
+            self.0.as_mut().unwrap()
+        }
+
+        /// Convert to the inner join handle.
+        ///
+        /// Dropping the join handle detaches the task instead of cancelling it.
+        pub fn into_inner(mut self) -> JoinHandle<T> {
+            self.0.take().unwrap()
+        }
+    }
+
+    impl<T: Send + 'static> Future for AsyncStdTask<T> {
+        type Output = T;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            match Pin::new(self.0.as_mut().unwrap()).poll(cx) {
+                Poll::Pending => Poll::Pending,
+                Poll::Ready(x) => {
+                    self.0 = None;
+                    Poll::Ready(x)
+                }
+            }
+        }
+    }
+
+    impl<T: Send + 'static> CancellableTask for AsyncStdTask<T> {
+        type Cancel<'a> = Pin<Box<dyn Future<Output = Option<T>> + Send>>;
+
+        fn cancel<'a>(mut self) -> Self::Cancel<'a> {
+            Box::pin(self.0.take().unwrap().cancel())
+        }
+    }
+
+    impl<T: Send + 'static> DetachableTask for AsyncStdTask<T> {
+        fn detach(mut self) {
+            // Dropping the async-std task automatically detaches it.
+            self.0 = None;
+        }
+    }
+
+    impl<T: Send + 'static> Drop for AsyncStdTask<T> {
+        fn drop(&mut self) {
+            if let Some(handle) = self.0.take() {
+                // Cancellation is asynchronous, so let the runtime drive it.
+                drop(task::spawn(async move {
+                    handle.cancel().await;
+                }));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async-std")]
+pub use async_std_impl::{AsyncStdGlobal, AsyncStdTask};
+
+#[cfg(feature = "blocking")]
+mod blocking_impl {
+    use crate::BlockingExecutor;
+    use async_task_crate::Task;
+
+    use core::convert::Infallible;
+
+    /// Implements [`BlockingExecutor`] for the thread pool of the `blocking` crate.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct BlockingPool {
+        _private: (),
+    }
+
+    impl<F: FnOnce() -> T + Send + 'static, T: Send + 'static> BlockingExecutor<F, T> for BlockingPool {
+        type Task = Task<T>;
+        type Error = Infallible;
+
+        fn try_spawn_blocking(&self, f: F) -> Result<Self::Task, Self::Error> {
+            Ok(blocking_crate::unblock(f))
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+pub use blocking_impl::BlockingPool;
+
+#[cfg(feature = "futures")]
+mod futures_impl {
+    use crate::{CancellableTask, DetachableTask, Executor, LocalExecutor};
+    use futures_crate::future::{FutureExt as _, FutureObj, LocalFutureObj, RemoteHandle};
+    use futures_crate::task::{LocalSpawn, LocalSpawnExt, Spawn, SpawnError, SpawnExt};
+
+    use core::future::{ready, Future, Ready};
+
+    /// Implements [`Executor`] for a [`futures::task::Spawn`].
+    ///
+    /// If the spawner also implements [`futures::task::LocalSpawn`], this also implements
+    /// [`LocalExecutor`].
+    ///
+    /// [`futures::task::Spawn`]: futures_crate::task::Spawn
+    /// [`futures::task::LocalSpawn`]: futures_crate::task::LocalSpawn
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct SpawnExecutor<S>(pub S);
+
+    impl<F: Future + Send + 'static, S: Spawn> Executor<F> for SpawnExecutor<S>
+    where
+        F::Output: Send + 'static,
+    {
+        type Task = RemoteHandle<F::Output>;
+        type Error = SpawnError;
+
+        fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+            self.0.spawn_with_handle(future)
+        }
+    }
+
+    impl<F: Future + 'static, S: LocalSpawn> LocalExecutor<F> for SpawnExecutor<S>
+    where
+        F::Output: 'static,
+    {
+        type Task = RemoteHandle<F::Output>;
+        type Error = SpawnError;
+
+        fn try_spawn_local(&self, future: F) -> Result<Self::Task, Self::Error> {
+            self.0.spawn_local_with_handle(future)
+        }
+    }
+
+    impl<T: 'static> CancellableTask for RemoteHandle<T> {
+        type Cancel<'a> = Ready<Option<T>>;
+
+        fn cancel<'a>(mut self) -> Self::Cancel<'a> {
+            // Take the output if the task has already completed. Dropping the handle drops the
+            // remote future otherwise.
+            let output = (&mut self).now_or_never();
+            drop(self);
+            ready(output)
+        }
+    }
+
+    impl<T: 'static> DetachableTask for RemoteHandle<T> {
+        fn detach(self) {
+            self.forget()
+        }
+    }
+
+    /// Implements [`futures::task::Spawn`] for an [`Executor`].
+    ///
+    /// Spawned tasks are detached.
+    ///
+    /// [`futures::task::Spawn`]: futures_crate::task::Spawn
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct ExecutorSpawner<E>(pub E);
+
+    impl<E: Executor<FutureObj<'static, ()>>> Spawn for ExecutorSpawner<E>
+    where
+        E::Task: DetachableTask,
+    {
+        fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+            match self.0.try_spawn(future) {
+                Ok(task) => {
+                    task.detach();
+                    Ok(())
+                }
+                Err(_) => Err(SpawnError::shutdown()),
+            }
+        }
+    }
+
+    impl<E: LocalExecutor<LocalFutureObj<'static, ()>>> LocalSpawn for ExecutorSpawner<E>
+    where
+        E::Task: DetachableTask,
+    {
+        fn spawn_local_obj(&self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
+            match self.0.try_spawn_local(future) {
+                Ok(task) => {
+                    task.detach();
+                    Ok(())
+                }
+                Err(_) => Err(SpawnError::shutdown()),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+pub use futures_impl::{ExecutorSpawner, SpawnExecutor};
+
+#[cfg(feature = "futures-executor")]
+mod futures_executor_impl {
+    use crate::{Executor, LocalExecutor};
+    use futures_crate::executor::{LocalSpawner, ThreadPool};
+    use futures_crate::future::RemoteHandle;
+    use futures_crate::task::{LocalSpawnExt, SpawnError, SpawnExt};
+
+    use core::future::Future;
+
+    impl<F: Future + Send + 'static> Executor<F> for ThreadPool
+    where
+        F::Output: Send + 'static,
+    {
+        type Task = RemoteHandle<F::Output>;
+        type Error = SpawnError;
+
+        fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+            self.spawn_with_handle(future)
+        }
+    }
+
+    impl<F: Future + 'static> Executor<F> for LocalSpawner
+    where
+        F::Output: 'static,
+    {
+        type Task = RemoteHandle<F::Output>;
+        type Error = SpawnError;
+
+        fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+            self.spawn_local_with_handle(future)
+        }
+    }
+
+    impl<F: Future + 'static> LocalExecutor<F> for LocalSpawner
+    where
+        F::Output: 'static,
+    {
+        type Task = RemoteHandle<F::Output>;
+        type Error = SpawnError;
+
+        fn try_spawn_local(&self, future: F) -> Result<Self::Task, Self::Error> {
+            self.spawn_local_with_handle(future)
+        }
+    }
+}
+
+#[cfg(feature = "smol")]
+mod smol_impl {
+    use crate::Executor;
+    use async_task_crate::Task;
+
+    use core::convert::Infallible;
+    use core::future::Future;
+
+    /// Implements traits for `smol`'s global executor.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct SmolGlobal {
+        _private: (),
+    }
+
+    impl<F: Future + Send + 'static> Executor<F> for SmolGlobal
+    where
+        F::Output: Send + 'static,
+    {
+        type Task = Task<F::Output>;
+        type Error = Infallible;
+
+        fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+            Ok(smol_crate::spawn(future))
+        }
+    }
+}
+
+#[cfg(feature = "smol")]
+pub use smol_impl::SmolGlobal;
+
+#[cfg(feature = "async-global-executor")]
+mod async_global_executor_impl {
+    use crate::{Executor, LocalExecutor};
+    use async_task_crate::Task;
+
+    use core::convert::Infallible;
+    use core::future::Future;
+
+    /// Implements traits for the `async-global-executor` crate.
+    ///
+    /// [`Executor`] spawns onto the global thread pool, while [`LocalExecutor`] spawns onto the
+    /// executor of the current thread.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct AsyncGlobalExecutor {
+        _private: (),
+    }
+
+    impl<F: Future + Send + 'static> Executor<F> for AsyncGlobalExecutor
+    where
+        F::Output: Send + 'static,
+    {
+        type Task = Task<F::Output>;
+        type Error = Infallible;
+
+        fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+            Ok(async_global_executor_crate::spawn(future))
+        }
+    }
+
+    impl<F: Future + 'static> LocalExecutor<F> for AsyncGlobalExecutor
+    where
+        F::Output: 'static,
+    {
+        type Task = Task<F::Output>;
+        type Error = Infallible;
+
+        fn try_spawn_local(&self, future: F) -> Result<Self::Task, Self::Error> {
+            Ok(async_global_executor_crate::spawn_local(future))
+        }
+    }
+}
+
+#[cfg(feature = "async-global-executor")]
+pub use async_global_executor_impl::AsyncGlobalExecutor;
+
+#[cfg(feature = "wasm")]
+mod wasm_impl {
+    use crate::remote::{remote_pair, RemoteCancel, RemoteTask};
+    use crate::{Executor, LocalExecutor};
+
+    use core::convert::Infallible;
+    use core::future::Future;
+
+    /// Implements traits for the browser's event loop, via `wasm-bindgen-futures`.
+    ///
+    /// Futures are spawned with [`spawn_local`](wasm_bindgen_futures::spawn_local), which
+    /// offers no task handle of its own. The returned [`WasmTask`] receives the output over a
+    /// channel instead. Cancelling or dropping it stops the future the next time it is woken.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct WasmGlobal {
+        _private: (),
+    }
+
+    /// Task type for [`WasmGlobal`].
+    pub type WasmTask<T> = RemoteTask<T>;
+
+    /// Future returned by cancelling a [`WasmTask`].
+    pub type WasmCancel<T> = RemoteCancel<T>;
+
+    impl<F: Future + 'static> Executor<F> for WasmGlobal
+    where
+        F::Output: 'static,
+    {
+        type Task = WasmTask<F::Output>;
+        type Error = Infallible;
+
+        fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+            let (task, remote) = remote_pair(future);
+            wasm_bindgen_futures::spawn_local(remote);
+            Ok(task)
+        }
+    }
+
+    impl<F: Future + 'static> LocalExecutor<F> for WasmGlobal
+    where
+        F::Output: 'static,
+    {
+        type Task = WasmTask<F::Output>;
+        type Error = Infallible;
+
+        fn try_spawn_local(&self, future: F) -> Result<Self::Task, Self::Error> {
+            self.try_spawn(future)
+        }
+    }
+}
+
+#[cfg(feature = "wasm")]
+pub use wasm_impl::{WasmCancel, WasmGlobal, WasmTask};
+
+#[cfg(all(feature = "tokio", feature = "timer"))]
+pub use tokio_impl::TokioTimer;
 #[cfg(feature = "tokio")]
-pub use tokio_impl::{TokioGlobal, TokioTask};
+pub use tokio_impl::{FallibleTokioCancel, FallibleTokioTask, TokioCancel, TokioGlobal, TokioTask};
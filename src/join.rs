@@ -0,0 +1,222 @@
+//! Fixed-arity combinators that do not allocate.
+//!
+//! [`join2`], [`join3`], [`or2`] and [`or3`] take futures with different output types.
+//! [`all_fixed`] and [`all_limited_fixed`] are the array-based form for futures of one type. Task
+//! handles are kept inline on the stack, so all of them work without `alloc`.
+
+use crate::Executor;
+
+use core::array;
+use core::future::{poll_fn, Future};
+use core::pin::{pin, Pin};
+use core::task::{Context, Poll};
+
+/// Spawn two futures onto `exec` and wait for both of them to complete.
+pub async fn join2<E, A, B>(
+    exec: E,
+    a: A,
+    b: B,
+) -> Result<(A::Output, B::Output), <E as Executor<A>>::Error>
+where
+    A: Future,
+    B: Future,
+    E: Executor<A> + Executor<B, Error = <E as Executor<A>>::Error>,
+{
+    let mut a = pin!(exec.try_spawn(a)?);
+    let mut b = pin!(exec.try_spawn(b)?);
+    let (mut out_a, mut out_b) = (None, None);
+
+    poll_fn(|cx| {
+        let done_a = poll_slot(a.as_mut(), &mut out_a, cx);
+        let done_b = poll_slot(b.as_mut(), &mut out_b, cx);
+
+        if done_a && done_b {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await;
+
+    Ok((out_a.unwrap(), out_b.unwrap()))
+}
+
+/// Spawn three futures onto `exec` and wait for all of them to complete.
+pub async fn join3<E, A, B, C>(
+    exec: E,
+    a: A,
+    b: B,
+    c: C,
+) -> Result<(A::Output, B::Output, C::Output), <E as Executor<A>>::Error>
+where
+    A: Future,
+    B: Future,
+    C: Future,
+    E: Executor<A>
+        + Executor<B, Error = <E as Executor<A>>::Error>
+        + Executor<C, Error = <E as Executor<A>>::Error>,
+{
+    let mut a = pin!(exec.try_spawn(a)?);
+    let mut b = pin!(exec.try_spawn(b)?);
+    let mut c = pin!(exec.try_spawn(c)?);
+    let (mut out_a, mut out_b, mut out_c) = (None, None, None);
+
+    poll_fn(|cx| {
+        let done_a = poll_slot(a.as_mut(), &mut out_a, cx);
+        let done_b = poll_slot(b.as_mut(), &mut out_b, cx);
+        let done_c = poll_slot(c.as_mut(), &mut out_c, cx);
+
+        if done_a && done_b && done_c {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await;
+
+    Ok((out_a.unwrap(), out_b.unwrap(), out_c.unwrap()))
+}
+
+/// Spawn two futures onto `exec` and return the output of whichever completes first.
+///
+/// The other task is dropped, which cancels it.
+pub async fn or2<E, T, A, B>(exec: E, a: A, b: B) -> Result<T, <E as Executor<A>>::Error>
+where
+    A: Future<Output = T>,
+    B: Future<Output = T>,
+    E: Executor<A> + Executor<B, Error = <E as Executor<A>>::Error>,
+{
+    let mut a = pin!(exec.try_spawn(a)?);
+    let mut b = pin!(exec.try_spawn(b)?);
+
+    Ok(poll_fn(|cx| {
+        if let Poll::Ready(output) = a.as_mut().poll(cx) {
+            return Poll::Ready(output);
+        }
+        b.as_mut().poll(cx)
+    })
+    .await)
+}
+
+/// Spawn three futures onto `exec` and return the output of whichever completes first.
+///
+/// The other tasks are dropped, which cancels them.
+pub async fn or3<E, T, A, B, C>(exec: E, a: A, b: B, c: C) -> Result<T, <E as Executor<A>>::Error>
+where
+    A: Future<Output = T>,
+    B: Future<Output = T>,
+    C: Future<Output = T>,
+    E: Executor<A>
+        + Executor<B, Error = <E as Executor<A>>::Error>
+        + Executor<C, Error = <E as Executor<A>>::Error>,
+{
+    let mut a = pin!(exec.try_spawn(a)?);
+    let mut b = pin!(exec.try_spawn(b)?);
+    let mut c = pin!(exec.try_spawn(c)?);
+
+    Ok(poll_fn(|cx| {
+        if let Poll::Ready(output) = a.as_mut().poll(cx) {
+            return Poll::Ready(output);
+        }
+        if let Poll::Ready(output) = b.as_mut().poll(cx) {
+            return Poll::Ready(output);
+        }
+        c.as_mut().poll(cx)
+    })
+    .await)
+}
+
+/// Spawn every future in an array onto `exec` and wait for all of them to complete.
+///
+/// Outputs are returned in the same order as `futures`. If spawning fails, every task that was already
+/// spawned is cancelled and the error is returned.
+pub async fn all_fixed<E, F, const N: usize>(
+    exec: E,
+    futures: [F; N],
+) -> Result<[F::Output; N], E::Error>
+where
+    F: Future,
+    E: Executor<F>,
+{
+    all_limited_fixed(exec, futures, N.max(1)).await
+}
+
+/// Like [`all_fixed`], but with at most `limit` tasks running at once.
+///
+/// # Panics
+///
+/// Panics if `limit` is zero.
+pub async fn all_limited_fixed<E, F, const N: usize>(
+    exec: E,
+    futures: [F; N],
+    limit: usize,
+) -> Result<[F::Output; N], E::Error>
+where
+    F: Future,
+    E: Executor<F>,
+{
+    assert!(limit > 0, "limit must be greater than zero");
+
+    let mut futures = futures.map(Some);
+    let mut tasks = pin!(array::from_fn::<Option<E::Task>, N, _>(|_| None));
+    let mut outputs = array::from_fn::<Option<F::Output>, N, _>(|_| None);
+    let (mut next, mut running) = (0, 0);
+
+    poll_fn(|cx| loop {
+        // Spawn futures until the limit is reached.
+        while running < limit && next < N {
+            let task = exec.try_spawn(futures[next].take().unwrap())?;
+            array_slot(tasks.as_mut(), next).set(Some(task));
+            next += 1;
+            running += 1;
+        }
+
+        let mut finished = false;
+        for (i, output) in outputs.iter_mut().enumerate() {
+            let mut slot = array_slot(tasks.as_mut(), i);
+            if let Some(task) = slot.as_mut().as_pin_mut() {
+                if let Poll::Ready(out) = task.poll(cx) {
+                    *output = Some(out);
+                    slot.set(None);
+                    running -= 1;
+                    finished = true;
+                }
+            }
+        }
+
+        if running == 0 && next == N {
+            return Poll::Ready(Ok(()));
+        }
+
+        // Only wait if no room was freed up for more tasks.
+        if !finished {
+            return Poll::Pending;
+        }
+    })
+    .await?;
+
+    Ok(outputs.map(Option::unwrap))
+}
+
+/// Get a pinned reference to one element of a pinned array.
+fn array_slot<T, const N: usize>(array: Pin<&mut [T; N]>, index: usize) -> Pin<&mut T> {
+    // SAFETY: The elements of a pinned array are never moved out of it.
+    unsafe { array.map_unchecked_mut(|array| &mut array[index]) }
+}
+
+/// Poll a task into `slot` unless it has already completed.
+///
+/// Returns whether the task has completed.
+fn poll_slot<T: Future>(
+    task: Pin<&mut T>,
+    slot: &mut Option<T::Output>,
+    cx: &mut Context<'_>,
+) -> bool {
+    if slot.is_none() {
+        if let Poll::Ready(output) = task.poll(cx) {
+            *slot = Some(output);
+        }
+    }
+
+    slot.is_some()
+}
@@ -8,12 +8,84 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+pub mod adapter;
 #[cfg(feature = "std")]
 mod boxed;
+#[cfg(feature = "ext")]
+pub mod ext;
+#[cfg(feature = "ffi")]
+mod ffi;
+#[cfg(feature = "std")]
+pub mod global;
 pub mod impls;
+mod join;
+#[cfg(feature = "priority")]
+mod priority;
+#[cfg(feature = "alloc")]
+mod remote;
+#[cfg(feature = "alloc")]
+pub mod scope;
+#[cfg(feature = "alloc")]
+mod spin;
+#[cfg(feature = "stream")]
+pub mod stream;
+pub mod task;
+#[cfg(feature = "std")]
+pub mod testing;
+#[cfg(all(
+    feature = "std",
+    not(all(target_family = "wasm", not(target_feature = "atomics")))
+))]
+mod thread;
+#[cfg(feature = "std")]
+mod timer;
+#[cfg(feature = "alloc")]
+mod token;
+#[cfg(feature = "std")]
+mod tracker;
+#[cfg(feature = "wasi")]
+mod wasi;
+#[cfg(feature = "alloc")]
+mod weak;
 
 #[cfg(feature = "std")]
-pub use boxed::{BoxedExecutor, LocalBoxedExecutor};
+pub use boxed::{
+    BoxedExecutor, DynExecutor, DynTask, InfallibleBoxedExecutor, InfallibleLocalBoxedExecutor,
+    LocalBoxedExecutor, LocalBoxedTask, LocalSpawnError, SpawnError,
+};
+#[cfg(feature = "ext")]
+pub use ext::{
+    all, all_indexed, all_infallible, all_limited, all_limited_infallible, all_limited_mut,
+    all_mut, all_unordered, all_unordered_limited, all_weighted, or, or_infallible, or_mut,
+    par_map_ordered, pipeline, race_ok, try_all, AllIndexed, AllUnordered, AnyExecutor,
+    AnyExecutorMut, ParMapOrdered, Pipeline, TaskMap, TaskSet,
+};
+#[cfg(all(feature = "ext", feature = "std"))]
+pub use ext::{spawn_retry, RetryPolicy};
+#[cfg(feature = "ffi")]
+pub use ffi::{HostCallback, HostExecutor, HostScheduleFn};
+pub use join::{all_fixed, all_limited_fixed, join2, join3, or2, or3};
+#[cfg(feature = "priority")]
+pub use priority::PrioritizedExecutor;
+#[cfg(feature = "alloc")]
+pub use remote::{remote_pair, Remote, RemoteCancel, RemoteExecutor, RemoteJob, RemoteTask};
+#[cfg(all(
+    feature = "std",
+    not(all(target_family = "wasm", not(target_feature = "atomics")))
+))]
+pub use thread::{
+    NewThread, PoolExecutor, PoolJob, ThreadExecutor, ThreadExecutorTask, ThreadTask,
+};
+#[cfg(feature = "std")]
+pub use timer::Timer;
+#[cfg(feature = "alloc")]
+pub use token::{CancellationToken, Cancelled};
+#[cfg(feature = "std")]
+pub use tracker::{TaskTracker, TrackedFuture, TrackerError};
+#[cfg(feature = "wasi")]
+pub use wasi::WasiExecutor;
+#[cfg(feature = "alloc")]
+pub use weak::{ExecutorGone, WeakError, WeakExecutor};
 
 use core::convert::Infallible;
 use core::future::Future;
@@ -31,9 +103,152 @@ pub trait FutureExt: Future + Sized {
     fn par<E: InfallibleExecutor<Self>>(self, ex: E) -> E::Task {
         ex.spawn(self)
     }
+
+    /// Spawn this future on an executor and detach the task so it runs in the background.
+    fn try_par_detach<E: Executor<Self>>(self, ex: E) -> Result<(), E::Error>
+    where
+        E::Task: DetachableTask,
+    {
+        ex.try_spawn(self).map(DetachableTask::detach)
+    }
+
+    /// Spawn this future on an executor infallibly and detach the task.
+    fn par_detach<E: InfallibleExecutor<Self>>(self, ex: E)
+    where
+        E::Task: DetachableTask,
+    {
+        ex.spawn(self).detach()
+    }
+
+    /// Spawn this future on a thread-local executor.
+    fn try_par_local<E: LocalExecutor<Self>>(self, ex: E) -> Result<E::Task, E::Error> {
+        ex.try_spawn_local(self)
+    }
+
+    /// Spawn this future on a thread-local executor infallibly.
+    fn par_local<E: LocalExecutor<Self, Error = Infallible>>(self, ex: E) -> E::Task {
+        match ex.try_spawn_local(self) {
+            Ok(task) => task,
+            Err(infl) => match infl {},
+        }
+    }
 }
 impl<F: Future + Sized> FutureExt for F {}
 
+/// Extension trait for a spawned task.
+///
+/// Tasks whose backend supports it also implement [`InspectableTask`].
+pub trait TaskExt: Future + Sized {
+    /// Map the output of this task with a closure.
+    fn map<M: FnOnce(Self::Output) -> U, U>(self, map: M) -> task::Map<Self, M> {
+        task::Map::new(self, map)
+    }
+
+    /// Cancel this task if it does not complete within `duration`.
+    ///
+    /// The task resolves to [`Elapsed`](adapter::Elapsed) if the timer fires first. This is
+    /// not named `with_timeout` so that it does not clash with
+    /// [`ExecutorExt::with_timeout`].
+    #[cfg(feature = "std")]
+    fn timeout<T: Timer>(
+        self,
+        duration: core::time::Duration,
+        timer: T,
+    ) -> adapter::TimeoutFuture<Self, T::Sleep> {
+        adapter::TimeoutFuture::new(self, timer.sleep(duration))
+    }
+
+    /// Detach this task when it is dropped, instead of cancelling it.
+    ///
+    /// The task can still be awaited as usual.
+    fn detach_on_drop(self) -> task::DetachOnDrop<Self>
+    where
+        Self: DetachableTask,
+    {
+        task::DetachOnDrop::new(self)
+    }
+}
+impl<T: Future + Sized> TaskExt for T {}
+
+/// Extension trait for an executor.
+pub trait ExecutorExt: Sized {
+    /// Convert errors that occur while spawning using a closure.
+    fn map_err<M>(self, map: M) -> adapter::MapErr<Self, M> {
+        adapter::MapErr::new(self, map)
+    }
+
+    /// Panic instead of returning an error if spawning fails.
+    fn infallible(self) -> adapter::Unwrapped<Self> {
+        adapter::Unwrapped::new(self)
+    }
+
+    /// Spawn onto `other` if spawning onto this executor fails.
+    #[cfg(feature = "std")]
+    fn or_else<B>(self, other: B) -> adapter::Fallback<Self, B> {
+        adapter::Fallback::new(self, other)
+    }
+
+    /// Give every task spawned onto this executor a name.
+    ///
+    /// The executor must implement [`MetadataExecutor`] for `&str`.
+    #[cfg(feature = "alloc")]
+    fn named(self, name: impl Into<alloc::string::String>) -> adapter::Named<Self> {
+        adapter::Named::new(self, name.into())
+    }
+
+    /// Spawn every task onto this executor with the given priority.
+    fn with_priority(self, priority: Priority) -> adapter::WithPriority<Self> {
+        adapter::WithPriority::new(self, priority)
+    }
+
+    /// Make tasks spawned onto this executor yield after `budget` polls in a row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `budget` is zero.
+    fn with_budget(self, budget: usize) -> adapter::Budgeted<Self> {
+        adapter::Budgeted::new(self, budget)
+    }
+
+    /// Make tasks spawned onto this executor resolve early once `token` is cancelled.
+    #[cfg(feature = "alloc")]
+    fn with_token(self, token: CancellationToken) -> adapter::WithToken<Self> {
+        adapter::WithToken::new(self, token)
+    }
+
+    /// Cancel tasks spawned onto this executor if they run for longer than `duration`.
+    #[cfg(feature = "std")]
+    fn with_timeout<T: Timer>(
+        self,
+        duration: core::time::Duration,
+        timer: T,
+    ) -> adapter::Timeout<Self, T> {
+        adapter::Timeout::new(self, duration, timer)
+    }
+
+    /// Convert this executor into a [`BoxedExecutor`].
+    #[cfg(feature = "std")]
+    fn boxed<'a>(self) -> BoxedExecutor<'a>
+    where
+        Self: DynExecutor<'a> + Send + Sync + 'a,
+    {
+        BoxedExecutor::new(self)
+    }
+
+    /// Convert this executor into a [`LocalBoxedExecutor`].
+    #[cfg(feature = "std")]
+    #[allow(clippy::type_complexity)]
+    fn boxed_local<'a>(self) -> LocalBoxedExecutor<'a>
+    where
+        Self: Executor<core::pin::Pin<alloc::boxed::Box<dyn Future<Output = ()> + 'a>>> + 'a,
+        Self::Task: 'a,
+        Self::Error: std::error::Error + 'static,
+    {
+        LocalBoxedExecutor::new(self)
+    }
+}
+impl<E> ExecutorExt for E {}
+
 /// Trait for an executor that [`Future`]s can be spawned onto.
 pub trait Executor<F: Future> {
     /// The task type produced by spawning a future.
@@ -46,6 +261,22 @@ pub trait Executor<F: Future> {
 
     /// Try to spawn the future on this executor.
     fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error>;
+
+    /// Try to spawn every future on this executor at once.
+    ///
+    /// By default this calls [`try_spawn`](Executor::try_spawn) for each future. Integrations
+    /// can override it to amortize per-spawn synchronization across the whole batch. If
+    /// spawning fails, the tasks that were already spawned are dropped.
+    #[cfg(feature = "alloc")]
+    fn try_spawn_all(
+        &self,
+        futures: alloc::vec::Vec<F>,
+    ) -> Result<alloc::vec::Vec<Self::Task>, Self::Error> {
+        futures
+            .into_iter()
+            .map(|future| self.try_spawn(future))
+            .collect()
+    }
 }
 
 impl<F: Future, E: Executor<F> + ?Sized> Executor<F> for &mut E {
@@ -56,6 +287,15 @@ impl<F: Future, E: Executor<F> + ?Sized> Executor<F> for &mut E {
     fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
         (**self).try_spawn(future)
     }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn try_spawn_all(
+        &self,
+        futures: alloc::vec::Vec<F>,
+    ) -> Result<alloc::vec::Vec<Self::Task>, Self::Error> {
+        (**self).try_spawn_all(futures)
+    }
 }
 
 impl<F: Future, E: Executor<F> + ?Sized> Executor<F> for &E {
@@ -66,16 +306,250 @@ impl<F: Future, E: Executor<F> + ?Sized> Executor<F> for &E {
     fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
         (**self).try_spawn(future)
     }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn try_spawn_all(
+        &self,
+        futures: alloc::vec::Vec<F>,
+    ) -> Result<alloc::vec::Vec<Self::Task>, Self::Error> {
+        (**self).try_spawn_all(futures)
+    }
+}
+
+/// Trait for an executor that needs exclusive access to spawn futures.
+///
+/// Some spawn handles, such as hand-rolled single-threaded schedulers, need `&mut self` to
+/// enqueue work. Wrapping one in a [`RefCell`](core::cell::RefCell), or a `Mutex` with the
+/// `std` feature, turns it into an [`Executor`] that works with every combinator.
+///
+/// A reference to any [`Executor`] is an `ExecutorMut`, as are `Rc` and `Arc` pointers to
+/// one. There is deliberately no blanket implementation for every `Executor`. It would reject
+/// an impl like `impl<F: Future> ExecutorMut<F> for MySpawner`, because another crate could
+/// implement `Executor<TheirFuture>` for `MySpawner` and the two impls would overlap.
+///
+/// The `ext` combinators have `_mut` variants, such as `all_mut`, that spawn through an
+/// `&mut` reference to an `ExecutorMut`.
+pub trait ExecutorMut<F: Future> {
+    /// The task type produced by spawning a future.
+    ///
+    /// It is assumed that dropping a task cancels it implicitly.
+    type Task: Future<Output = F::Output>;
+
+    /// The error type that can occur while spawning.
+    type Error;
+
+    /// Try to spawn the future on this executor.
+    ///
+    /// This is not named `try_spawn` so that it does not clash with [`Executor::try_spawn`].
+    fn try_spawn_mut(&mut self, future: F) -> Result<Self::Task, Self::Error>;
+}
+
+impl<F: Future, E: Executor<F> + ?Sized> ExecutorMut<F> for &E {
+    type Task = E::Task;
+    type Error = E::Error;
+
+    #[inline]
+    fn try_spawn_mut(&mut self, future: F) -> Result<Self::Task, Self::Error> {
+        (**self).try_spawn(future)
+    }
+}
+
+impl<F: Future, E: ExecutorMut<F> + ?Sized> ExecutorMut<F> for &mut E {
+    type Task = E::Task;
+    type Error = E::Error;
+
+    #[inline]
+    fn try_spawn_mut(&mut self, future: F) -> Result<Self::Task, Self::Error> {
+        (**self).try_spawn_mut(future)
+    }
+}
+
+impl<F: Future, E: ExecutorMut<F> + ?Sized> Executor<F> for core::cell::RefCell<E> {
+    type Task = E::Task;
+    type Error = E::Error;
+
+    /// # Panics
+    ///
+    /// Panics if the executor is already borrowed, such as when spawning from within
+    /// [`ExecutorMut::try_spawn_mut`].
+    #[inline]
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        self.borrow_mut().try_spawn_mut(future)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: Future, E: ExecutorMut<F> + ?Sized> Executor<F> for std::sync::Mutex<E> {
+    type Task = E::Task;
+    type Error = E::Error;
+
+    #[inline]
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        self.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .try_spawn_mut(future)
+    }
+}
+
+/// Trait for an executor that can attach metadata, such as a name, to spawned tasks.
+///
+/// Integrations forward the metadata to the underlying runtime, where it can be used for
+/// debugging.
+pub trait MetadataExecutor<F: Future, M>: Executor<F> {
+    /// Try to spawn the future on this executor, attaching `meta` to the task.
+    fn try_spawn_with(&self, future: F, meta: M) -> Result<Self::Task, Self::Error>;
+}
+
+impl<F: Future, M, E: MetadataExecutor<F, M> + ?Sized> MetadataExecutor<F, M> for &mut E {
+    #[inline]
+    fn try_spawn_with(&self, future: F, meta: M) -> Result<Self::Task, Self::Error> {
+        (**self).try_spawn_with(future, meta)
+    }
+}
+
+impl<F: Future, M, E: MetadataExecutor<F, M> + ?Sized> MetadataExecutor<F, M> for &E {
+    #[inline]
+    fn try_spawn_with(&self, future: F, meta: M) -> Result<Self::Task, Self::Error> {
+        (**self).try_spawn_with(future, meta)
+    }
+}
+
+/// The priority of a spawned task.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Background work that only runs when nothing else can.
+    Low,
+
+    /// The default priority.
+    #[default]
+    Normal,
+
+    /// Latency-sensitive work that runs before anything else.
+    High,
+}
+
+/// Trait for an executor that can run some tasks ahead of others.
+pub trait PriorityExecutor<F: Future>: Executor<F> {
+    /// Try to spawn the future on this executor with the given priority.
+    fn try_spawn_with_priority(
+        &self,
+        future: F,
+        priority: Priority,
+    ) -> Result<Self::Task, Self::Error>;
+}
+
+impl<F: Future, E: PriorityExecutor<F> + ?Sized> PriorityExecutor<F> for &mut E {
+    #[inline]
+    fn try_spawn_with_priority(
+        &self,
+        future: F,
+        priority: Priority,
+    ) -> Result<Self::Task, Self::Error> {
+        (**self).try_spawn_with_priority(future, priority)
+    }
+}
+
+impl<F: Future, E: PriorityExecutor<F> + ?Sized> PriorityExecutor<F> for &E {
+    #[inline]
+    fn try_spawn_with_priority(
+        &self,
+        future: F,
+        priority: Priority,
+    ) -> Result<Self::Task, Self::Error> {
+        (**self).try_spawn_with_priority(future, priority)
+    }
+}
+
+/// Trait for an executor that spawns futures onto the current thread.
+///
+/// Neither the future nor the task produced by spawning it need to be `Send`.
+pub trait LocalExecutor<F: Future> {
+    /// The task type produced by spawning a future.
+    ///
+    /// It is assumed that dropping a task cancels it implicitly.
+    type Task: Future<Output = F::Output>;
+
+    /// The error type that can occur while spawning.
+    type Error;
+
+    /// Try to spawn the future on this executor.
+    fn try_spawn_local(&self, future: F) -> Result<Self::Task, Self::Error>;
+}
+
+impl<F: Future, E: LocalExecutor<F> + ?Sized> LocalExecutor<F> for &mut E {
+    type Task = E::Task;
+    type Error = E::Error;
+
+    #[inline]
+    fn try_spawn_local(&self, future: F) -> Result<Self::Task, Self::Error> {
+        (**self).try_spawn_local(future)
+    }
+}
+
+impl<F: Future, E: LocalExecutor<F> + ?Sized> LocalExecutor<F> for &E {
+    type Task = E::Task;
+    type Error = E::Error;
+
+    #[inline]
+    fn try_spawn_local(&self, future: F) -> Result<Self::Task, Self::Error> {
+        (**self).try_spawn_local(future)
+    }
+}
+
+/// Trait for an executor that runs blocking closures away from the async runtime.
+pub trait BlockingExecutor<F: FnOnce() -> T, T> {
+    /// The task type produced by spawning a closure.
+    type Task: Future<Output = T>;
+
+    /// The error type that can occur while spawning.
+    type Error;
+
+    /// Try to spawn the closure on this executor.
+    fn try_spawn_blocking(&self, f: F) -> Result<Self::Task, Self::Error>;
+}
+
+impl<F: FnOnce() -> T, T, E: BlockingExecutor<F, T> + ?Sized> BlockingExecutor<F, T> for &E {
+    type Task = E::Task;
+    type Error = E::Error;
+
+    #[inline]
+    fn try_spawn_blocking(&self, f: F) -> Result<Self::Task, Self::Error> {
+        (**self).try_spawn_blocking(f)
+    }
+}
+
+impl<F: FnOnce() -> T, T, E: BlockingExecutor<F, T> + ?Sized> BlockingExecutor<F, T> for &mut E {
+    type Task = E::Task;
+    type Error = E::Error;
+
+    #[inline]
+    fn try_spawn_blocking(&self, f: F) -> Result<Self::Task, Self::Error> {
+        (**self).try_spawn_blocking(f)
+    }
 }
 
 /// Trait for a task that can be canceled.
-// TODO: GAT and TAIT
-pub trait CancellableTask<'a>: Future + 'a {
+pub trait CancellableTask: Future {
     /// The future returned by trying to cancel this task.
-    type Cancel: Future<Output = Option<Self::Output>> + 'a;
+    ///
+    /// It may borrow from the task for `'a`.
+    type Cancel<'a>: Future<Output = Option<Self::Output>> + 'a
+    where
+        Self: 'a;
 
     /// Cancel this future.
-    fn cancel(self) -> Self::Cancel;
+    fn cancel<'a>(self) -> Self::Cancel<'a>
+    where
+        Self: 'a;
+}
+
+/// Trait for a task that can be canceled, without a lifetime parameter.
+#[cfg(feature = "afit")]
+pub trait AsyncCancellableTask: Future {
+    /// Cancel this task, returning its output if it had already completed.
+    #[allow(async_fn_in_trait)]
+    async fn cancel(self) -> Option<Self::Output>;
 }
 
 /// Trait for a task that can be detached to run forever.
@@ -84,6 +558,14 @@ pub trait DetachableTask: Future {
     fn detach(self);
 }
 
+/// Trait for a task that can tell whether it has finished without being polled.
+pub trait InspectableTask: Future {
+    /// Tell whether the task has finished running.
+    ///
+    /// Once this returns `true`, polling the task will not return `Poll::Pending`.
+    fn is_finished(&self) -> bool;
+}
+
 /// Executors that are infallible.
 pub trait InfallibleExecutor<F: Future>: Executor<F, Error = Infallible> {
     /// Spawn a task infallibly.
@@ -96,12 +578,34 @@ pub trait InfallibleExecutor<F: Future>: Executor<F, Error = Infallible> {
 }
 impl<F: Future, E: Executor<F, Error = Infallible>> InfallibleExecutor<F> for E {}
 
+/// Extension trait for results whose error can never occur.
+///
+/// Combinators such as [`join2`] or [`all_fixed`] return the executor's spawn error. When the
+/// executor is infallible, use [`unwrap_infallible`](UnwrapInfallible::unwrap_infallible) to
+/// get the output without a panicking `unwrap`. The `ext` combinators also have `_infallible`
+/// variants, such as `all_infallible`, that do this for you.
+pub trait UnwrapInfallible<T> {
+    /// Get the success value of this result.
+    fn unwrap_infallible(self) -> T;
+}
+
+impl<T> UnwrapInfallible<T> for Result<T, Infallible> {
+    #[inline]
+    fn unwrap_infallible(self) -> T {
+        match self {
+            Ok(value) => value,
+            Err(infl) => match infl {},
+        }
+    }
+}
+
 #[cfg(feature = "alloc")]
 mod alloc_impls {
-    use super::Executor;
+    use super::{BlockingExecutor, Executor, ExecutorMut, LocalExecutor};
     use alloc::boxed::Box;
     use alloc::rc::Rc;
     use alloc::sync::Arc;
+    use alloc::vec::Vec;
     use core::future::Future;
 
     impl<F: Future, E: Executor<F> + ?Sized> Executor<F> for Box<E> {
@@ -112,6 +616,11 @@ mod alloc_impls {
         fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
             (**self).try_spawn(future)
         }
+
+        #[inline]
+        fn try_spawn_all(&self, futures: Vec<F>) -> Result<Vec<Self::Task>, Self::Error> {
+            (**self).try_spawn_all(futures)
+        }
     }
 
     impl<F: Future, E: Executor<F> + ?Sized> Executor<F> for Rc<E> {
@@ -122,6 +631,11 @@ mod alloc_impls {
         fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
             (**self).try_spawn(future)
         }
+
+        #[inline]
+        fn try_spawn_all(&self, futures: Vec<F>) -> Result<Vec<Self::Task>, Self::Error> {
+            (**self).try_spawn_all(futures)
+        }
     }
 
     impl<F: Future, E: Executor<F> + ?Sized> Executor<F> for Arc<E> {
@@ -132,5 +646,100 @@ mod alloc_impls {
         fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
             (**self).try_spawn(future)
         }
+
+        #[inline]
+        fn try_spawn_all(&self, futures: Vec<F>) -> Result<Vec<Self::Task>, Self::Error> {
+            (**self).try_spawn_all(futures)
+        }
+    }
+
+    impl<F: Future, E: ExecutorMut<F> + ?Sized> ExecutorMut<F> for Box<E> {
+        type Task = E::Task;
+        type Error = E::Error;
+
+        #[inline]
+        fn try_spawn_mut(&mut self, future: F) -> Result<Self::Task, Self::Error> {
+            (**self).try_spawn_mut(future)
+        }
+    }
+
+    impl<F: Future, E: Executor<F> + ?Sized> ExecutorMut<F> for Rc<E> {
+        type Task = E::Task;
+        type Error = E::Error;
+
+        #[inline]
+        fn try_spawn_mut(&mut self, future: F) -> Result<Self::Task, Self::Error> {
+            (**self).try_spawn(future)
+        }
+    }
+
+    impl<F: Future, E: Executor<F> + ?Sized> ExecutorMut<F> for Arc<E> {
+        type Task = E::Task;
+        type Error = E::Error;
+
+        #[inline]
+        fn try_spawn_mut(&mut self, future: F) -> Result<Self::Task, Self::Error> {
+            (**self).try_spawn(future)
+        }
+    }
+
+    impl<F: Future, E: LocalExecutor<F> + ?Sized> LocalExecutor<F> for Box<E> {
+        type Task = E::Task;
+        type Error = E::Error;
+
+        #[inline]
+        fn try_spawn_local(&self, future: F) -> Result<Self::Task, Self::Error> {
+            (**self).try_spawn_local(future)
+        }
+    }
+
+    impl<F: Future, E: LocalExecutor<F> + ?Sized> LocalExecutor<F> for Rc<E> {
+        type Task = E::Task;
+        type Error = E::Error;
+
+        #[inline]
+        fn try_spawn_local(&self, future: F) -> Result<Self::Task, Self::Error> {
+            (**self).try_spawn_local(future)
+        }
+    }
+
+    impl<F: Future, E: LocalExecutor<F> + ?Sized> LocalExecutor<F> for Arc<E> {
+        type Task = E::Task;
+        type Error = E::Error;
+
+        #[inline]
+        fn try_spawn_local(&self, future: F) -> Result<Self::Task, Self::Error> {
+            (**self).try_spawn_local(future)
+        }
+    }
+
+    impl<F: FnOnce() -> T, T, E: BlockingExecutor<F, T> + ?Sized> BlockingExecutor<F, T> for Box<E> {
+        type Task = E::Task;
+        type Error = E::Error;
+
+        #[inline]
+        fn try_spawn_blocking(&self, f: F) -> Result<Self::Task, Self::Error> {
+            (**self).try_spawn_blocking(f)
+        }
+    }
+
+    impl<F: FnOnce() -> T, T, E: BlockingExecutor<F, T> + ?Sized> BlockingExecutor<F, T> for Rc<E> {
+        type Task = E::Task;
+        type Error = E::Error;
+
+        #[inline]
+        fn try_spawn_blocking(&self, f: F) -> Result<Self::Task, Self::Error> {
+            (**self).try_spawn_blocking(f)
+        }
+    }
+
+    impl<F: FnOnce() -> T, T, E: BlockingExecutor<F, T> + ?Sized> BlockingExecutor<F, T> for Arc<E> {
+        type Task = E::Task;
+        type Error = E::Error;
+
+        #[inline]
+        fn try_spawn_blocking(&self, f: F) -> Result<Self::Task, Self::Error> {
+            (**self).try_spawn_blocking(f)
+        }
     }
 }
@@ -0,0 +1,93 @@
+//! An `async-executor` based executor with priority lanes.
+
+use crate::{Executor, Priority, PriorityExecutor};
+use async_executor_crate::Task;
+
+use alloc::boxed::Box;
+
+use core::convert::Infallible;
+use core::future::{poll_fn, Future};
+use core::pin::pin;
+use core::task::Poll;
+
+/// An executor that runs higher priority tasks before lower priority ones.
+///
+/// This holds one [`async_executor::Executor`] per [`Priority`]. While it is being
+/// [`run`](Self::run), a task is only polled if no task of a higher priority is ready.
+///
+/// [`async_executor::Executor`]: async_executor_crate::Executor
+#[derive(Debug, Default)]
+pub struct PrioritizedExecutor<'a> {
+    /// Executors in order of decreasing priority.
+    lanes: [async_executor_crate::Executor<'a>; 3],
+}
+
+impl<'a> PrioritizedExecutor<'a> {
+    /// Create a new `PrioritizedExecutor`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lane(&self, priority: Priority) -> &async_executor_crate::Executor<'a> {
+        match priority {
+            Priority::High => &self.lanes[0],
+            Priority::Normal => &self.lanes[1],
+            Priority::Low => &self.lanes[2],
+        }
+    }
+
+    /// Run a single ready task of the highest available priority, returning `false` if there
+    /// were none.
+    pub fn try_tick(&self) -> bool {
+        self.lanes.iter().any(|lane| lane.try_tick())
+    }
+
+    /// Run spawned tasks in priority order until `future` completes.
+    pub async fn run<T>(&self, future: impl Future<Output = T>) -> T {
+        let mut future = pin!(future);
+        let mut ticks = self.lanes.each_ref().map(|lane| Box::pin(lane.tick()));
+
+        poll_fn(|cx| {
+            if let Poll::Ready(output) = future.as_mut().poll(cx) {
+                return Poll::Ready(output);
+            }
+
+            // Run at most one task per poll, so that the highest lane is checked again first.
+            for (tick, lane) in ticks.iter_mut().zip(&self.lanes) {
+                if tick.as_mut().poll(cx).is_ready() {
+                    *tick = Box::pin(lane.tick());
+                    cx.waker().wake_by_ref();
+                    break;
+                }
+            }
+
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+impl<'a, F: Future + Send + 'a> Executor<F> for PrioritizedExecutor<'a>
+where
+    F::Output: Send + 'a,
+{
+    type Task = Task<F::Output>;
+    type Error = Infallible;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        self.try_spawn_with_priority(future, Priority::Normal)
+    }
+}
+
+impl<'a, F: Future + Send + 'a> PriorityExecutor<F> for PrioritizedExecutor<'a>
+where
+    F::Output: Send + 'a,
+{
+    fn try_spawn_with_priority(
+        &self,
+        future: F,
+        priority: Priority,
+    ) -> Result<Self::Task, Self::Error> {
+        Ok(self.lane(priority).spawn(future))
+    }
+}
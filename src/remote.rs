@@ -0,0 +1,293 @@
+//! Task handles for executors that only support fire-and-forget spawning.
+
+use crate::{CancellableTask, DetachableTask, Executor, InspectableTask};
+
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicU8, Ordering};
+use core::task::{Context, Poll};
+
+use atomic_waker::AtomicWaker;
+use pin_project_lite::pin_project;
+
+/// Set once the output has been written.
+const COMPLETE: u8 = 1 << 0;
+
+/// Set once the `Remote` has been dropped.
+const DONE: u8 = 1 << 1;
+
+/// Set if the task has been cancelled.
+const CANCELLED: u8 = 1 << 2;
+
+/// Set if the task has been detached.
+const DETACHED: u8 = 1 << 3;
+
+struct Shared<T> {
+    state: AtomicU8,
+
+    /// The output of the future, owned by the `Remote` until `COMPLETE` is set and by the task
+    /// handle after.
+    output: UnsafeCell<Option<T>>,
+
+    /// Waker for the task handle.
+    task_waker: AtomicWaker,
+
+    /// Waker for the remote future.
+    remote_waker: AtomicWaker,
+}
+
+// SAFETY: Access to the output is handed from the `Remote` to the task handle by the `COMPLETE`
+// flag, so only one side touches it at a time.
+unsafe impl<T: Send> Send for Shared<T> {}
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    fn state(&self) -> u8 {
+        self.state.load(Ordering::Acquire)
+    }
+
+    /// Take the output once the remote future has completed or been dropped.
+    ///
+    /// Must only be called from the task handle.
+    fn poll_output(&self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.task_waker.register(cx.waker());
+
+        let state = self.state();
+        if state & COMPLETE != 0 {
+            // SAFETY: The `Remote` no longer touches the output once `COMPLETE` is set.
+            Poll::Ready(unsafe { (*self.output.get()).take() })
+        } else if state & DONE != 0 {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Split a future into a task handle and a future to hand to an executor.
+///
+/// This lets integrations for executors that only support fire-and-forget spawning provide a
+/// proper task. Spawn the returned [`Remote`] onto the executor, and hand the [`RemoteTask`]
+/// out as the task. The task resolves to the output of `future`, and implements
+/// [`CancellableTask`] and [`DetachableTask`].
+pub fn remote_pair<F: Future>(future: F) -> (RemoteTask<F::Output>, Remote<F>) {
+    let shared = Arc::new(Shared {
+        state: AtomicU8::new(0),
+        output: UnsafeCell::new(None),
+        task_waker: AtomicWaker::new(),
+        remote_waker: AtomicWaker::new(),
+    });
+
+    (
+        RemoteTask {
+            shared: Some(shared.clone()),
+        },
+        Remote {
+            future,
+            guard: RemoteGuard(shared),
+        },
+    )
+}
+
+/// A handle to a future running on another executor.
+///
+/// Dropping this handle cancels the future the next time it is woken.
+pub struct RemoteTask<T> {
+    shared: Option<Arc<Shared<T>>>,
+}
+
+impl<T> fmt::Debug for RemoteTask<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RemoteTask { .. }")
+    }
+}
+
+impl<T> RemoteTask<T> {
+    fn cancel_inner(&self) {
+        let shared = self.shared.as_ref().unwrap();
+        shared.state.fetch_or(CANCELLED, Ordering::AcqRel);
+        shared.remote_waker.wake();
+    }
+}
+
+impl<T> Future for RemoteTask<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.shared.as_ref().unwrap().poll_output(cx) {
+            Poll::Ready(Some(output)) => Poll::Ready(output),
+            Poll::Ready(None) => panic!("remote future was dropped before completing"),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<T> Drop for RemoteTask<T> {
+    fn drop(&mut self) {
+        if self.shared.is_some() {
+            self.cancel_inner();
+        }
+    }
+}
+
+impl<T> CancellableTask for RemoteTask<T> {
+    type Cancel<'a>
+        = RemoteCancel<T>
+    where
+        T: 'a;
+
+    fn cancel<'a>(mut self) -> Self::Cancel<'a>
+    where
+        T: 'a,
+    {
+        self.cancel_inner();
+        RemoteCancel {
+            shared: self.shared.take().unwrap(),
+        }
+    }
+}
+
+impl<T> InspectableTask for RemoteTask<T> {
+    fn is_finished(&self) -> bool {
+        self.shared.as_ref().unwrap().state() & (COMPLETE | DONE) != 0
+    }
+}
+
+impl<T> DetachableTask for RemoteTask<T> {
+    fn detach(mut self) {
+        if let Some(shared) = self.shared.take() {
+            shared.state.fetch_or(DETACHED, Ordering::AcqRel);
+        }
+    }
+}
+
+/// Future returned by cancelling a [`RemoteTask`].
+///
+/// Resolves once the remote future has been dropped, returning its output if it had completed.
+pub struct RemoteCancel<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> fmt::Debug for RemoteCancel<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RemoteCancel { .. }")
+    }
+}
+
+impl<T> Future for RemoteCancel<T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.shared.poll_output(cx)
+    }
+}
+
+pin_project! {
+    /// The future to hand to the executor for a [`RemoteTask`].
+    ///
+    /// Created by [`remote_pair`]. It stops early once the task is cancelled or dropped.
+    pub struct Remote<F: Future> {
+        #[pin]
+        future: F,
+        guard: RemoteGuard<F::Output>,
+    }
+}
+
+impl<F: Future> fmt::Debug for Remote<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Remote { .. }")
+    }
+}
+
+impl<F: Future> Future for Remote<F> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.project();
+
+        let shared = &this.guard.0;
+        shared.remote_waker.register(cx.waker());
+        if shared.state() & CANCELLED != 0 {
+            return Poll::Ready(());
+        }
+
+        let output = match this.future.poll(cx) {
+            Poll::Ready(output) => output,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        if shared.state() & (CANCELLED | DETACHED) == 0 {
+            // SAFETY: The task handle does not touch the output until `COMPLETE` is set.
+            unsafe { *shared.output.get() = Some(output) };
+        }
+        shared.state.fetch_or(COMPLETE, Ordering::AcqRel);
+        shared.task_waker.wake();
+
+        Poll::Ready(())
+    }
+}
+
+/// Marks the remote future as done when it is dropped.
+struct RemoteGuard<T>(Arc<Shared<T>>);
+
+impl<T> Drop for RemoteGuard<T> {
+    fn drop(&mut self) {
+        self.0.state.fetch_or(DONE, Ordering::AcqRel);
+        self.0.task_waker.wake();
+    }
+}
+
+/// A boxed [`Remote`] handed to the spawn function of a [`RemoteExecutor`].
+pub type RemoteJob = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// An [`Executor`] for runtimes that can only spawn futures of one fixed type.
+///
+/// Some runtimes, such as `embassy-executor`, cannot spawn arbitrary futures, only instances
+/// of a task type declared up front. Declare a task that awaits a [`RemoteJob`] and wrap its
+/// spawner in a closure; each spawned future is boxed, split with [`remote_pair`], and handed
+/// to the closure, while the caller gets a [`RemoteTask`] for its output. Errors returned by
+/// the closure, such as a full task pool, are surfaced as the spawn error.
+///
+/// Only `alloc` is required, so this works on embedded targets with a global allocator.
+#[derive(Clone, Copy)]
+pub struct RemoteExecutor<S> {
+    spawn: S,
+}
+
+impl<S> fmt::Debug for RemoteExecutor<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RemoteExecutor { .. }")
+    }
+}
+
+impl<S> RemoteExecutor<S> {
+    /// Create an executor that hands jobs to `spawn`.
+    pub fn new(spawn: S) -> Self {
+        Self { spawn }
+    }
+
+    /// Convert to the inner closure.
+    pub fn into_inner(self) -> S {
+        self.spawn
+    }
+}
+
+impl<F: Future + Send + 'static, S: Fn(RemoteJob) -> Result<(), E>, E> Executor<F>
+    for RemoteExecutor<S>
+where
+    F::Output: Send + 'static,
+{
+    type Task = RemoteTask<F::Output>;
+    type Error = E;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        let (task, remote) = remote_pair(future);
+        (self.spawn)(Box::pin(remote))?;
+        Ok(task)
+    }
+}
@@ -0,0 +1,325 @@
+//! Structured concurrency over any [`Executor`].
+//!
+//! Every task spawned in a [`scope`] has either completed or been cancelled by the time the
+//! scope resolves. Use [`scope_send`] for a scope that can itself run on a multi-threaded
+//! executor.
+
+use crate::spin::SpinLock;
+use crate::{CancellableTask, Executor};
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use core::cell::RefCell;
+use core::future::{poll_fn, Future};
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use pin_project_lite::pin_project;
+
+/// Run `body` with a [`Scope`] that tasks can be spawned into.
+///
+/// Once `body` completes, every task spawned into the scope that has not already finished is
+/// awaited before this future resolves. Spawned futures may borrow data, as long as the
+/// executor allows it.
+///
+/// The returned future is not `Send`, so a scope cannot be moved between threads or nested in
+/// a task on a multi-threaded executor. Use [`scope_send`] for that.
+pub async fn scope<'env, E, R>(
+    exec: E,
+    body: impl for<'scope> FnOnce(
+        &'scope Scope<'scope, 'env, E>,
+    ) -> Pin<Box<dyn Future<Output = R> + 'scope>>,
+) -> R {
+    let scope = Scope {
+        exec,
+        tasks: RefCell::new(Vec::new()),
+        _scope: PhantomData,
+    };
+
+    let result = body(&scope).await;
+
+    // Wait for every task that is still running.
+    loop {
+        let next = scope.tasks.borrow_mut().pop();
+        match next {
+            Some(task) => poll_fn(|cx| task.borrow_mut().poll_join(cx)).await,
+            None => break,
+        }
+    }
+
+    result
+}
+
+/// A scope that tasks can be spawned into.
+///
+/// Created by [`scope`].
+pub struct Scope<'scope, 'env: 'scope, E> {
+    exec: E,
+    tasks: RefCell<Vec<Rc<RefCell<dyn ScopeEntry<'env> + 'env>>>>,
+    _scope: PhantomData<&'scope mut &'scope ()>,
+}
+
+impl<'scope, 'env, E> Scope<'scope, 'env, E> {
+    /// Get a reference to the underlying executor.
+    pub fn executor(&self) -> &E {
+        &self.exec
+    }
+
+    /// Spawn a future into this scope.
+    pub fn spawn<F: Future>(&self, future: F) -> Result<ScopedTask<'scope, E::Task>, E::Error>
+    where
+        E: Executor<F>,
+        E::Task: CancellableTask + Unpin + 'env,
+    {
+        let task = self.exec.try_spawn(future)?;
+        let slot = Rc::new(RefCell::new(Some(task)));
+        self.tasks.borrow_mut().push(slot.clone());
+
+        Ok(ScopedTask {
+            slot,
+            _scope: PhantomData,
+        })
+    }
+
+    /// Cancel every task in this scope that has not yet completed.
+    pub async fn cancel_all(&self) {
+        let tasks = core::mem::take(&mut *self.tasks.borrow_mut());
+        for task in tasks {
+            let cancel = task.borrow_mut().cancel();
+            if let Some(cancel) = cancel {
+                cancel.await;
+            }
+        }
+    }
+}
+
+/// A task spawned into a [`Scope`].
+///
+/// Resolves to `None` if the task was cancelled by [`Scope::cancel_all`] before it completed.
+/// Dropping this handle does not cancel the task; the scope still waits for it.
+pub struct ScopedTask<'scope, T> {
+    slot: Rc<RefCell<Option<T>>>,
+    _scope: PhantomData<&'scope ()>,
+}
+
+impl<'scope, T: Future + Unpin> ScopedTask<'scope, T> {
+    /// Cancel this task, returning its output if it had already completed.
+    pub async fn cancel(self) -> Option<T::Output>
+    where
+        T: CancellableTask,
+    {
+        let task = self.slot.borrow_mut().take()?;
+        task.cancel().await
+    }
+}
+
+impl<'scope, T: Future + Unpin> Future for ScopedTask<'scope, T> {
+    type Output = Option<T::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.slot.borrow_mut();
+        let task = match slot.as_mut() {
+            Some(task) => task,
+            None => return Poll::Ready(None),
+        };
+
+        match Pin::new(task).poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(output) => {
+                *slot = None;
+                Poll::Ready(Some(output))
+            }
+        }
+    }
+}
+
+/// Run `body` with a [`SendScope`] that tasks can be spawned into.
+///
+/// This is the thread-safe version of [`scope`]. The body and the spawned tasks must be `Send`,
+/// and in turn the returned future is `Send` as long as the executor is `Send + Sync`.
+pub async fn scope_send<'env, E, R>(
+    exec: E,
+    body: impl for<'scope> FnOnce(
+        &'scope SendScope<'scope, 'env, E>,
+    ) -> Pin<Box<dyn Future<Output = R> + Send + 'scope>>,
+) -> R {
+    let scope = SendScope {
+        exec,
+        tasks: SpinLock::new(Vec::new()),
+        _scope: PhantomData,
+    };
+
+    let result = body(&scope).await;
+
+    // Wait for every task that is still running.
+    loop {
+        let next = scope.tasks.lock().pop();
+        match next {
+            Some(task) => poll_fn(|cx| task.lock().poll_join(cx)).await,
+            None => break,
+        }
+    }
+
+    result
+}
+
+/// A thread-safe scope that tasks can be spawned into.
+///
+/// Created by [`scope_send`].
+pub struct SendScope<'scope, 'env: 'scope, E> {
+    exec: E,
+    tasks: SpinLock<Vec<Arc<SpinLock<dyn SendScopeEntry<'env> + 'env>>>>,
+    _scope: PhantomData<&'scope mut &'scope ()>,
+}
+
+impl<'scope, 'env, E> SendScope<'scope, 'env, E> {
+    /// Get a reference to the underlying executor.
+    pub fn executor(&self) -> &E {
+        &self.exec
+    }
+
+    /// Spawn a future into this scope.
+    pub fn spawn<F: Future>(&self, future: F) -> Result<SendScopedTask<'scope, E::Task>, E::Error>
+    where
+        E: Executor<F>,
+        E::Task: CancellableTask + Unpin + Send + 'env,
+        <E::Task as CancellableTask>::Cancel<'env>: Send,
+    {
+        let task = self.exec.try_spawn(future)?;
+        let slot = Arc::new(SpinLock::new(Some(task)));
+        self.tasks.lock().push(slot.clone());
+
+        Ok(SendScopedTask {
+            slot,
+            _scope: PhantomData,
+        })
+    }
+
+    /// Cancel every task in this scope that has not yet completed.
+    pub async fn cancel_all(&self) {
+        let tasks = core::mem::take(&mut *self.tasks.lock());
+        for task in tasks {
+            let cancel = task.lock().cancel();
+            if let Some(cancel) = cancel {
+                cancel.await;
+            }
+        }
+    }
+}
+
+/// A task spawned into a [`SendScope`].
+///
+/// Resolves to `None` if the task was cancelled by [`SendScope::cancel_all`] before it
+/// completed. Dropping this handle does not cancel the task; the scope still waits for it.
+pub struct SendScopedTask<'scope, T> {
+    slot: Arc<SpinLock<Option<T>>>,
+    _scope: PhantomData<&'scope ()>,
+}
+
+impl<'scope, T: Future + Unpin> SendScopedTask<'scope, T> {
+    /// Cancel this task, returning its output if it had already completed.
+    pub async fn cancel(self) -> Option<T::Output>
+    where
+        T: CancellableTask,
+    {
+        let task = self.slot.lock().take()?;
+        task.cancel().await
+    }
+}
+
+impl<'scope, T: Future + Unpin> Future for SendScopedTask<'scope, T> {
+    type Output = Option<T::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.slot.lock();
+        let task = match slot.as_mut() {
+            Some(task) => task,
+            None => return Poll::Ready(None),
+        };
+
+        match Pin::new(task).poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(output) => {
+                *slot = None;
+                Poll::Ready(Some(output))
+            }
+        }
+    }
+}
+
+/// Type-erased task held by a scope.
+trait ScopeEntry<'env> {
+    /// Poll the task until it completes, discarding its output.
+    fn poll_join(&mut self, cx: &mut Context<'_>) -> Poll<()>;
+
+    /// Cancel the task if it is still running.
+    fn cancel(&mut self) -> Option<Pin<Box<dyn Future<Output = ()> + 'env>>>;
+}
+
+impl<'env, T: CancellableTask + Unpin + 'env> ScopeEntry<'env> for Option<T> {
+    fn poll_join(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        match self {
+            Some(task) => match Pin::new(task).poll(cx) {
+                Poll::Pending => Poll::Pending,
+                Poll::Ready(_) => {
+                    *self = None;
+                    Poll::Ready(())
+                }
+            },
+            None => Poll::Ready(()),
+        }
+    }
+
+    fn cancel(&mut self) -> Option<Pin<Box<dyn Future<Output = ()> + 'env>>> {
+        let cancel = self.take()?.cancel();
+        Some(Box::pin(async move {
+            cancel.await;
+        }))
+    }
+}
+
+/// Thread-safe type-erased task held by a [`SendScope`].
+trait SendScopeEntry<'env>: Send {
+    /// Poll the task until it completes, discarding its output.
+    fn poll_join(&mut self, cx: &mut Context<'_>) -> Poll<()>;
+
+    /// Cancel the task if it is still running.
+    fn cancel(&mut self) -> Option<Pin<Box<dyn Future<Output = ()> + Send + 'env>>>;
+}
+
+impl<'env, T> SendScopeEntry<'env> for Option<T>
+where
+    T: CancellableTask + Unpin + Send + 'env,
+    T::Cancel<'env>: Send,
+{
+    fn poll_join(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        ScopeEntry::poll_join(self, cx)
+    }
+
+    fn cancel(&mut self) -> Option<Pin<Box<dyn Future<Output = ()> + Send + 'env>>> {
+        let cancel = self.take()?.cancel::<'env>();
+        Some(Box::pin(Discard { future: cancel }))
+    }
+}
+
+pin_project! {
+    /// Future that discards the output of the inner future.
+    ///
+    /// Unlike an `async` block, this is `Send` whenever the inner future is.
+    struct Discard<F> {
+        #[pin]
+        future: F,
+    }
+}
+
+impl<F: Future> Future for Discard<F> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().future.poll(cx).map(drop)
+    }
+}
@@ -0,0 +1,141 @@
+//! Spawning a future for every item of a [`Stream`].
+
+use crate::ext::{OrderedTasks, TaskSet};
+use crate::Executor;
+
+use alloc::boxed::Box;
+
+use core::fmt;
+use core::future::{poll_fn, Future};
+use core::pin::{pin, Pin};
+use core::task::{Context, Poll};
+
+use futures_core::Stream;
+
+/// Spawn `f(item)` onto `exec` for every item of `stream`, with at most `limit` tasks running
+/// at once.
+///
+/// Resolves once the stream is exhausted and every task has completed. If spawning fails, the
+/// remaining tasks are cancelled and the error is returned.
+///
+/// # Panics
+///
+/// Panics if `limit` is zero.
+pub async fn for_each_concurrent<E, S, M, Fut>(
+    exec: E,
+    stream: S,
+    limit: usize,
+    mut f: M,
+) -> Result<(), E::Error>
+where
+    S: Stream,
+    M: FnMut(S::Item) -> Fut,
+    Fut: Future<Output = ()>,
+    E: Executor<Fut>,
+{
+    assert!(limit > 0, "limit must be greater than zero");
+
+    let mut stream = pin!(stream);
+    let mut done = false;
+    let mut set = TaskSet::new(exec);
+
+    poll_fn(|cx| loop {
+        // Spawn items until the limit is reached.
+        while !done && set.len() < limit {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => set.spawn(f(item))?,
+                Poll::Ready(None) => done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        match set.poll_next(cx) {
+            Poll::Ready(Some(())) => continue,
+            Poll::Ready(None) if done => return Poll::Ready(Ok(())),
+            _ => return Poll::Pending,
+        }
+    })
+    .await
+}
+
+/// Spawn `f(item)` onto `exec` for every item of `stream`, yielding outputs in input order.
+///
+/// At most `limit` tasks are alive at once, counting those whose outputs are waiting for earlier
+/// tasks to complete. If spawning fails, the error is yielded and no further items are taken
+/// from the stream.
+///
+/// # Panics
+///
+/// Panics if `limit` is zero.
+pub fn par_map<E, S, M, Fut>(exec: E, stream: S, limit: usize, f: M) -> ParMap<E, S, M, Fut>
+where
+    S: Stream,
+    M: FnMut(S::Item) -> Fut,
+    Fut: Future,
+    E: Executor<Fut>,
+{
+    assert!(limit > 0, "limit must be greater than zero");
+
+    ParMap {
+        tasks: OrderedTasks::new(exec),
+        stream: Some(Box::pin(stream)),
+        f,
+        limit,
+    }
+}
+
+/// Stream returned by [`par_map`].
+pub struct ParMap<E: Executor<Fut>, S, M, Fut: Future> {
+    tasks: OrderedTasks<E, Fut>,
+    stream: Option<Pin<Box<S>>>,
+    f: M,
+    limit: usize,
+}
+
+impl<E: Executor<Fut>, S, M, Fut: Future> fmt::Debug for ParMap<E, S, M, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ParMap { .. }")
+    }
+}
+
+// The stream and tasks are boxed and the other fields are never pinned.
+impl<E: Executor<Fut>, S, M, Fut: Future> Unpin for ParMap<E, S, M, Fut> {}
+
+impl<E, S, M, Fut> Stream for ParMap<E, S, M, Fut>
+where
+    S: Stream,
+    M: FnMut(S::Item) -> Fut,
+    Fut: Future,
+    E: Executor<Fut>,
+{
+    type Item = Result<Fut::Output, E::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        // Spawn items until the limit is reached.
+        while this.tasks.len() < this.limit {
+            let stream = match &mut this.stream {
+                Some(stream) => stream,
+                None => break,
+            };
+
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    if let Err(err) = this.tasks.spawn((this.f)(item)) {
+                        this.stream = None;
+                        return Poll::Ready(Some(Err(err)));
+                    }
+                }
+                Poll::Ready(None) => this.stream = None,
+                Poll::Pending => break,
+            }
+        }
+
+        match this.tasks.poll_next(cx) {
+            Poll::Ready(Some(output)) => Poll::Ready(Some(Ok(output))),
+            Poll::Ready(None) if this.stream.is_none() => Poll::Ready(None),
+            _ => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! Wrappers around spawned tasks.
+
+use crate::{CancellableTask, DetachableTask, InspectableTask};
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Task whose output is mapped with a closure.
+    ///
+    /// Created by [`TaskExt::map`](crate::TaskExt::map).
+    pub struct Map<T, M> {
+        #[pin]
+        task: T,
+        map: Option<M>,
+    }
+}
+
+impl<T, M> fmt::Debug for Map<T, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Map { .. }")
+    }
+}
+
+impl<T, M> Map<T, M> {
+    pub(crate) fn new(task: T, map: M) -> Self {
+        Self {
+            task,
+            map: Some(map),
+        }
+    }
+
+    /// Get a reference to the inner task.
+    pub fn get_ref(&self) -> &T {
+        &self.task
+    }
+}
+
+impl<T: Future, M: FnOnce(T::Output) -> U, U> Future for Map<T, M> {
+    type Output = U;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let output = match this.task.poll(cx) {
+            Poll::Ready(output) => output,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        let map = this.map.take().expect("`Map` polled after completion");
+        Poll::Ready(map(output))
+    }
+}
+
+impl<T: CancellableTask, M: FnOnce(T::Output) -> U, U> CancellableTask for Map<T, M> {
+    type Cancel<'a>
+        = MapCancel<T::Cancel<'a>, M>
+    where
+        Self: 'a;
+
+    fn cancel<'a>(self) -> Self::Cancel<'a>
+    where
+        Self: 'a,
+    {
+        MapCancel {
+            cancel: self.task.cancel(),
+            map: self.map,
+        }
+    }
+}
+
+impl<T: DetachableTask, M: FnOnce(T::Output) -> U, U> DetachableTask for Map<T, M> {
+    fn detach(self) {
+        self.task.detach();
+    }
+}
+
+impl<T: InspectableTask, M: FnOnce(T::Output) -> U, U> InspectableTask for Map<T, M> {
+    fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+}
+
+pin_project! {
+    /// Future returned by cancelling a [`Map`].
+    pub struct MapCancel<C, M> {
+        #[pin]
+        cancel: C,
+        map: Option<M>,
+    }
+}
+
+impl<C, M> fmt::Debug for MapCancel<C, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MapCancel { .. }")
+    }
+}
+
+impl<T, C: Future<Output = Option<T>>, M: FnOnce(T) -> U, U> Future for MapCancel<C, M> {
+    type Output = Option<U>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let output = match this.cancel.poll(cx) {
+            Poll::Ready(output) => output,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        let map = this
+            .map
+            .take()
+            .expect("`MapCancel` polled after completion");
+        Poll::Ready(output.map(map))
+    }
+}
+
+/// Task that is detached instead of cancelled when it is dropped.
+///
+/// Created by [`TaskExt::detach_on_drop`](crate::TaskExt::detach_on_drop).
+pub struct DetachOnDrop<T: DetachableTask> {
+    task: Option<T>,
+}
+
+impl<T: DetachableTask + fmt::Debug> fmt::Debug for DetachOnDrop<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DetachOnDrop")
+            .field("task", &self.task)
+            .finish()
+    }
+}
+
+impl<T: DetachableTask> DetachOnDrop<T> {
+    pub(crate) fn new(task: T) -> Self {
+        Self { task: Some(task) }
+    }
+
+    /// Convert back to the inner task, which is cancelled when dropped again.
+    pub fn into_inner(mut self) -> T {
+        self.task.take().unwrap()
+    }
+}
+
+impl<T: DetachableTask + Unpin> Future for DetachOnDrop<T> {
+    type Output = T::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let task = self.get_mut().task.as_mut().unwrap();
+        Pin::new(task).poll(cx)
+    }
+}
+
+impl<T: DetachableTask + InspectableTask + Unpin> InspectableTask for DetachOnDrop<T> {
+    fn is_finished(&self) -> bool {
+        self.task.as_ref().unwrap().is_finished()
+    }
+}
+
+impl<T: DetachableTask> Drop for DetachOnDrop<T> {
+    fn drop(&mut self) {
+        // The task is only ever pinned through `Unpin`, so it can be moved out here.
+        if let Some(task) = self.task.take() {
+            task.detach();
+        }
+    }
+}
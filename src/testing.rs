@@ -0,0 +1,189 @@
+//! Deterministic executor for testing code that is generic over [`Executor`].
+
+use crate::remote::{remote_pair, RemoteTask};
+use crate::{Executor, LocalExecutor};
+
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use core::cell::{Cell, RefCell};
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Waker};
+
+use std::sync::Mutex;
+use std::task::Wake;
+
+/// An executor that only runs futures when told to.
+///
+/// Spawned futures are queued until [`step`](Self::step) or
+/// [`run_until_stalled`](Self::run_until_stalled) is called, and may borrow from the
+/// environment. Spawning can be made to fail with [`fail_next`](Self::fail_next), to exercise
+/// error paths.
+#[allow(clippy::type_complexity)]
+pub struct TestExecutor<'a> {
+    /// Spawned futures, indexed by task ID. Completed slots are left empty.
+    tasks: RefCell<Vec<Option<Pin<Box<dyn Future<Output = ()> + 'a>>>>>,
+
+    /// IDs of tasks that have been woken.
+    ready: Arc<Mutex<VecDeque<usize>>>,
+
+    /// Wakers for each task, indexed by task ID.
+    wakers: RefCell<Vec<Arc<TaskWaker>>>,
+
+    /// Number of upcoming spawns that should fail.
+    failures: Cell<usize>,
+}
+
+/// Task type for [`TestExecutor`].
+pub type TestTask<T> = RemoteTask<T>;
+
+/// Error returned by [`TestExecutor`] when a spawn failure was injected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestSpawnError {
+    _private: (),
+}
+
+impl fmt::Display for TestSpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("injected spawn failure")
+    }
+}
+
+impl std::error::Error for TestSpawnError {}
+
+struct TaskWaker {
+    id: usize,
+    queued: AtomicBool,
+    ready: Arc<Mutex<VecDeque<usize>>>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        if !self.queued.swap(true, Ordering::SeqCst) {
+            self.ready.lock().unwrap().push_back(self.id);
+        }
+    }
+}
+
+impl fmt::Debug for TestExecutor<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TestExecutor")
+            .field("len", &self.len())
+            .field("failures", &self.failures.get())
+            .finish()
+    }
+}
+
+impl Default for TestExecutor<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> TestExecutor<'a> {
+    /// Create a new `TestExecutor`.
+    pub fn new() -> Self {
+        Self {
+            tasks: RefCell::new(Vec::new()),
+            ready: Arc::new(Mutex::new(VecDeque::new())),
+            wakers: RefCell::new(Vec::new()),
+            failures: Cell::new(0),
+        }
+    }
+
+    /// Make the next `count` spawns fail with a [`TestSpawnError`].
+    ///
+    /// Pass `usize::MAX` to make every spawn fail.
+    pub fn fail_next(&self, count: usize) {
+        self.failures.set(count);
+    }
+
+    /// Get the number of tasks that have not yet completed or been cancelled.
+    pub fn len(&self) -> usize {
+        self.tasks.borrow().iter().filter(|t| t.is_some()).count()
+    }
+
+    /// Tell whether every task has completed or been cancelled.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Poll a single woken task, returning `false` if there were none.
+    pub fn step(&self) -> bool {
+        loop {
+            let id = match self.ready.lock().unwrap().pop_front() {
+                Some(id) => id,
+                None => return false,
+            };
+
+            let waker = self.wakers.borrow()[id].clone();
+            waker.queued.store(false, Ordering::SeqCst);
+
+            // Take the future out, so that it can spawn more tasks while being polled.
+            let mut future = match self.tasks.borrow_mut()[id].take() {
+                Some(future) => future,
+                None => continue,
+            };
+
+            let waker = Waker::from(waker);
+            let mut cx = Context::from_waker(&waker);
+            if future.as_mut().poll(&mut cx).is_pending() {
+                self.tasks.borrow_mut()[id] = Some(future);
+            }
+
+            return true;
+        }
+    }
+
+    /// Poll woken tasks until none of them can make progress.
+    pub fn run_until_stalled(&self) {
+        while self.step() {}
+    }
+}
+
+impl<'a, F: Future + 'a> Executor<F> for TestExecutor<'a> {
+    type Task = TestTask<F::Output>;
+    type Error = TestSpawnError;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        let failures = self.failures.get();
+        if failures > 0 {
+            if failures != usize::MAX {
+                self.failures.set(failures - 1);
+            }
+            return Err(TestSpawnError { _private: () });
+        }
+
+        let (task, remote) = remote_pair(future);
+
+        let mut tasks = self.tasks.borrow_mut();
+        let waker = Arc::new(TaskWaker {
+            id: tasks.len(),
+            queued: AtomicBool::new(false),
+            ready: self.ready.clone(),
+        });
+        tasks.push(Some(Box::pin(remote)));
+        self.wakers.borrow_mut().push(waker.clone());
+
+        waker.wake();
+        Ok(task)
+    }
+}
+
+impl<'a, F: Future + 'a> LocalExecutor<F> for TestExecutor<'a> {
+    type Task = TestTask<F::Output>;
+    type Error = TestSpawnError;
+
+    fn try_spawn_local(&self, future: F) -> Result<Self::Task, Self::Error> {
+        self.try_spawn(future)
+    }
+}
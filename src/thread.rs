@@ -0,0 +1,214 @@
+//! Running work on dedicated OS threads.
+
+use crate::remote::{remote_pair, RemoteTask};
+use crate::{BlockingExecutor, DetachableTask, Executor, InspectableTask, MetadataExecutor};
+
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+
+use core::convert::Infallible;
+use core::fmt;
+use core::future::Future;
+use core::pin::{pin, Pin};
+use core::task::{Context, Poll, Waker};
+
+use std::io;
+use std::task::Wake;
+use std::thread::{self, Thread};
+
+/// A [`BlockingExecutor`] that runs each closure on a newly spawned thread.
+///
+/// This is a fallback for when no thread pool is available.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NewThread {
+    _private: (),
+}
+
+impl<F: FnOnce() -> T + Send + 'static, T: Send + 'static> BlockingExecutor<F, T> for NewThread {
+    type Task = ThreadTask<T>;
+    type Error = io::Error;
+
+    fn try_spawn_blocking(&self, f: F) -> Result<Self::Task, Self::Error> {
+        let (task, remote) = remote_pair(async move { f() });
+        thread::Builder::new().spawn(move || block_on(remote))?;
+        Ok(ThreadTask::new(task))
+    }
+}
+
+/// A closure running on another thread.
+///
+/// Dropping this task detaches it, so the closure still runs to completion.
+pub struct ThreadTask<T>(Option<RemoteTask<T>>);
+
+impl<T> ThreadTask<T> {
+    fn new(task: RemoteTask<T>) -> Self {
+        Self(Some(task))
+    }
+}
+
+impl<T> fmt::Debug for ThreadTask<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ThreadTask { .. }")
+    }
+}
+
+impl<T> Future for ThreadTask<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(self.0.as_mut().unwrap()).poll(cx)
+    }
+}
+
+impl<T> Drop for ThreadTask<T> {
+    fn drop(&mut self) {
+        if let Some(task) = self.0.take() {
+            task.detach();
+        }
+    }
+}
+
+impl<T> DetachableTask for ThreadTask<T> {
+    fn detach(self) {
+        // Dropping the handle detaches it.
+    }
+}
+
+impl<T> InspectableTask for ThreadTask<T> {
+    fn is_finished(&self) -> bool {
+        self.0.as_ref().unwrap().is_finished()
+    }
+}
+
+/// An [`Executor`] that runs each future on a newly spawned thread.
+///
+/// The thread blocks on the future until it completes or its task is cancelled.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThreadExecutor {
+    _private: (),
+}
+
+/// Task type for [`ThreadExecutor`].
+pub type ThreadExecutorTask<T> = RemoteTask<T>;
+
+impl<F: Future + Send + 'static> Executor<F> for ThreadExecutor
+where
+    F::Output: Send + 'static,
+{
+    type Task = ThreadExecutorTask<F::Output>;
+    type Error = io::Error;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        let (task, remote) = remote_pair(future);
+        thread::Builder::new().spawn(move || block_on(remote))?;
+        Ok(task)
+    }
+}
+
+/// Names the spawned thread.
+impl<F: Future + Send + 'static> MetadataExecutor<F, &str> for ThreadExecutor
+where
+    F::Output: Send + 'static,
+{
+    fn try_spawn_with(&self, future: F, name: &str) -> Result<Self::Task, Self::Error> {
+        let (task, remote) = remote_pair(future);
+        thread::Builder::new()
+            .name(name.into())
+            .spawn(move || block_on(remote))?;
+        Ok(task)
+    }
+}
+
+/// A boxed job handed to a thread pool by [`PoolExecutor`].
+pub type PoolJob = Box<dyn FnOnce() + Send + 'static>;
+
+/// An [`Executor`] and [`BlockingExecutor`] backed by any thread pool.
+///
+/// The pool is described by a closure that runs a job on one of its threads, such as
+/// `|job| rayon::spawn(job)`. Each spawned future is run to completion on a pool thread, which
+/// blocks while the future is pending, so this suits compute-heavy futures that rarely yield.
+#[derive(Clone, Copy)]
+pub struct PoolExecutor<S> {
+    spawn: S,
+}
+
+impl<S> fmt::Debug for PoolExecutor<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PoolExecutor { .. }")
+    }
+}
+
+impl<S: Fn(PoolJob)> PoolExecutor<S> {
+    /// Create an executor that runs jobs with `spawn`.
+    pub fn new(spawn: S) -> Self {
+        Self { spawn }
+    }
+
+    /// Run a closure on the pool, returning a task for its output.
+    ///
+    /// The closure runs even if the task is dropped before the pool gets to it.
+    pub fn spawn_compute<F: FnOnce() -> T + Send + 'static, T: Send + 'static>(
+        &self,
+        f: F,
+    ) -> ThreadTask<T> {
+        let (task, remote) = remote_pair(async move { f() });
+        (self.spawn)(Box::new(move || block_on(remote)));
+        ThreadTask::new(task)
+    }
+
+    /// Convert to the inner closure.
+    pub fn into_inner(self) -> S {
+        self.spawn
+    }
+}
+
+impl<F: Future + Send + 'static, S: Fn(PoolJob)> Executor<F> for PoolExecutor<S>
+where
+    F::Output: Send + 'static,
+{
+    type Task = RemoteTask<F::Output>;
+    type Error = Infallible;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        let (task, remote) = remote_pair(future);
+        (self.spawn)(Box::new(move || block_on(remote)));
+        Ok(task)
+    }
+}
+
+impl<F: FnOnce() -> T + Send + 'static, T: Send + 'static, S: Fn(PoolJob)> BlockingExecutor<F, T>
+    for PoolExecutor<S>
+{
+    type Task = ThreadTask<T>;
+    type Error = Infallible;
+
+    fn try_spawn_blocking(&self, f: F) -> Result<Self::Task, Self::Error> {
+        Ok(self.spawn_compute(f))
+    }
+}
+
+/// Block the current thread on a future.
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct Unparker(Thread);
+
+    impl Wake for Unparker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+
+        fn wake_by_ref(self: &Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(Unparker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        thread::park();
+    }
+}
@@ -0,0 +1,36 @@
+//! Runtime-agnostic timers.
+
+use core::future::Future;
+use core::time::Duration;
+
+use std::time::Instant;
+
+/// Trait for a timer that can sleep until a deadline.
+///
+/// This keeps combinators that need delays independent of any particular runtime.
+pub trait Timer {
+    /// The future returned by sleeping.
+    type Sleep: Future<Output = ()>;
+
+    /// Sleep until `deadline` is reached.
+    fn sleep_until(&self, deadline: Instant) -> Self::Sleep;
+
+    /// Sleep for `duration`.
+    fn sleep(&self, duration: Duration) -> Self::Sleep {
+        self.sleep_until(Instant::now() + duration)
+    }
+}
+
+impl<T: Timer + ?Sized> Timer for &T {
+    type Sleep = T::Sleep;
+
+    #[inline]
+    fn sleep_until(&self, deadline: Instant) -> Self::Sleep {
+        (**self).sleep_until(deadline)
+    }
+
+    #[inline]
+    fn sleep(&self, duration: Duration) -> Self::Sleep {
+        (**self).sleep(duration)
+    }
+}
@@ -0,0 +1,156 @@
+//! Cancelling groups of tasks across executors.
+
+use crate::spin::{SpinGuard, SpinLock};
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll, Waker};
+
+/// A token that can be used to cancel a group of tasks at once.
+///
+/// Clones of the token share the same state. Spawn tasks with
+/// [`ExecutorExt::with_token`](crate::ExecutorExt::with_token) to make them resolve early once
+/// [`cancel_all`](Self::cancel_all) is called.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    wakers: SpinLock<Wakers>,
+}
+
+impl Inner {
+    fn lock(&self) -> SpinGuard<'_, Wakers> {
+        self.wakers.lock()
+    }
+}
+
+/// Registered wakers, indexed by key.
+#[derive(Default)]
+struct Wakers {
+    slots: Vec<Option<Waker>>,
+    free: Vec<usize>,
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+impl CancellationToken {
+    /// Create a new `CancellationToken`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel every task using this token.
+    pub fn cancel_all(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+
+        let wakers = {
+            let mut wakers = self.inner.lock();
+            wakers.free.clear();
+            core::mem::take(&mut wakers.slots)
+        };
+
+        for waker in wakers.into_iter().flatten() {
+            waker.wake();
+        }
+    }
+
+    /// Tell whether this token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Wait until this token is cancelled.
+    pub fn cancelled(&self) -> Cancelled {
+        Cancelled {
+            registration: Registration::new(self.clone()),
+        }
+    }
+}
+
+/// Future returned by [`CancellationToken::cancelled`].
+#[derive(Debug)]
+pub struct Cancelled {
+    registration: Registration,
+}
+
+impl Future for Cancelled {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.registration.poll_cancelled(cx) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// A waker registered with a token, which is removed on drop.
+#[derive(Debug)]
+pub(crate) struct Registration {
+    token: CancellationToken,
+    key: Option<usize>,
+}
+
+impl Registration {
+    pub(crate) fn new(token: CancellationToken) -> Self {
+        Self { token, key: None }
+    }
+
+    /// Tell whether the token is cancelled, registering the waker if not.
+    pub(crate) fn poll_cancelled(&mut self, cx: &mut Context<'_>) -> bool {
+        if self.token.is_cancelled() {
+            return true;
+        }
+
+        let mut wakers = self.token.inner.lock();
+
+        // `cancel_all` sets the flag before taking the wakers, so if the token was cancelled
+        // after the check above, the slots may already be gone.
+        if self.token.is_cancelled() {
+            return true;
+        }
+
+        let waker = Some(cx.waker().clone());
+        if let Some(slot) = self.key.and_then(|key| wakers.slots.get_mut(key)) {
+            *slot = waker;
+        } else if let Some(key) = wakers.free.pop() {
+            wakers.slots[key] = waker;
+            self.key = Some(key);
+        } else {
+            wakers.slots.push(waker);
+            self.key = Some(wakers.slots.len() - 1);
+        }
+
+        false
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        if let Some(key) = self.key {
+            if !self.token.is_cancelled() {
+                let mut wakers = self.token.inner.lock();
+                if let Some(slot) = wakers.slots.get_mut(key) {
+                    *slot = None;
+                    wakers.free.push(key);
+                }
+            }
+        }
+    }
+}
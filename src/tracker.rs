@@ -0,0 +1,194 @@
+//! Waiting for a group of tasks, including detached ones.
+
+use crate::Executor;
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+use pin_project_lite::pin_project;
+
+use std::sync::{Mutex, MutexGuard};
+
+/// Tracks spawned tasks so that they can be waited on at shutdown.
+///
+/// Tasks spawned through [`spawn`](Self::spawn) are tracked until their future completes or is
+/// dropped, even if the task handle is detached. Once the tracker is [`close`](Self::close)d,
+/// [`wait`](Self::wait) resolves when no tracked tasks remain.
+#[derive(Clone, Default)]
+pub struct TaskTracker {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner(Mutex<State>);
+
+#[derive(Default)]
+struct State {
+    count: usize,
+    closed: bool,
+    waiters: Vec<Waker>,
+}
+
+impl Inner {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Wake waiters if the tracker is closed and empty.
+    fn notify(&self, mut state: MutexGuard<'_, State>) {
+        if state.closed && state.count == 0 {
+            let waiters = core::mem::take(&mut state.waiters);
+            drop(state);
+            waiters.into_iter().for_each(Waker::wake);
+        }
+    }
+}
+
+impl fmt::Debug for TaskTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.inner.lock();
+        f.debug_struct("TaskTracker")
+            .field("len", &state.count)
+            .field("closed", &state.closed)
+            .finish()
+    }
+}
+
+impl TaskTracker {
+    /// Create a new, open `TaskTracker`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn a future onto `exec` and track it.
+    ///
+    /// Fails with [`TrackerError::Closed`] if the tracker has been closed.
+    pub fn spawn<E: Executor<TrackedFuture<F>>, F: Future>(
+        &self,
+        exec: E,
+        future: F,
+    ) -> Result<E::Task, TrackerError<E::Error>> {
+        if self.is_closed() {
+            return Err(TrackerError::Closed);
+        }
+
+        exec.try_spawn(self.track_future(future))
+            .map_err(TrackerError::Spawn)
+    }
+
+    /// Track a future without spawning it.
+    ///
+    /// The future counts as a tracked task until it completes or is dropped.
+    pub fn track_future<F: Future>(&self, future: F) -> TrackedFuture<F> {
+        self.inner.lock().count += 1;
+        TrackedFuture {
+            future,
+            guard: TrackGuard(self.inner.clone()),
+        }
+    }
+
+    /// Stop new tasks from being spawned through this tracker.
+    pub fn close(&self) {
+        let mut state = self.inner.lock();
+        state.closed = true;
+        self.inner.notify(state);
+    }
+
+    /// Tell whether this tracker has been closed.
+    pub fn is_closed(&self) -> bool {
+        self.inner.lock().closed
+    }
+
+    /// Get the number of tracked tasks that have not yet completed.
+    pub fn len(&self) -> usize {
+        self.inner.lock().count
+    }
+
+    /// Tell whether every tracked task has completed.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Wait until this tracker is closed and every tracked task has completed.
+    pub async fn wait(&self) {
+        core::future::poll_fn(|cx| {
+            let mut state = self.inner.lock();
+            if state.closed && state.count == 0 {
+                Poll::Ready(())
+            } else {
+                if !state.waiters.iter().any(|w| w.will_wake(cx.waker())) {
+                    state.waiters.push(cx.waker().clone());
+                }
+                Poll::Pending
+            }
+        })
+        .await
+    }
+}
+
+pin_project! {
+    /// A future tracked by a [`TaskTracker`].
+    pub struct TrackedFuture<F> {
+        #[pin]
+        future: F,
+        guard: TrackGuard,
+    }
+}
+
+impl<F> fmt::Debug for TrackedFuture<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TrackedFuture { .. }")
+    }
+}
+
+impl<F: Future> Future for TrackedFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().future.poll(cx)
+    }
+}
+
+/// Untracks a task when its future is dropped.
+struct TrackGuard(Arc<Inner>);
+
+impl Drop for TrackGuard {
+    fn drop(&mut self) {
+        let mut state = self.0.lock();
+        state.count -= 1;
+        self.0.notify(state);
+    }
+}
+
+/// Error returned by [`TaskTracker::spawn`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackerError<E> {
+    /// The tracker has been closed.
+    Closed,
+
+    /// The executor failed to spawn the future.
+    Spawn(E),
+}
+
+impl<E: fmt::Display> fmt::Display for TrackerError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrackerError::Closed => f.write_str("task tracker is closed"),
+            TrackerError::Spawn(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for TrackerError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TrackerError::Closed => None,
+            TrackerError::Spawn(err) => Some(err),
+        }
+    }
+}
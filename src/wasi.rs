@@ -0,0 +1,161 @@
+//! A single-threaded executor suitable for WASI.
+
+use crate::{Executor, LocalExecutor};
+use async_task_crate::{Runnable, Task};
+
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+
+use core::convert::Infallible;
+use core::future::Future;
+use core::marker::PhantomData;
+use core::pin::pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll, Waker};
+
+use std::sync::Mutex;
+use std::task::Wake;
+
+/// A single-threaded executor that does not rely on threads being available.
+///
+/// Spawned futures do not need to be `Send`. On targets without threads (such as
+/// `wasm32-wasip1`), [`block_on`](Self::block_on) panics instead of sleeping when nothing can
+/// make progress, since no other thread could ever wake it up.
+pub struct WasiExecutor {
+    shared: Arc<Shared>,
+
+    /// Tasks must be run on the thread that spawned them.
+    _local: PhantomData<*const ()>,
+}
+
+struct Shared {
+    queue: Mutex<VecDeque<Runnable>>,
+    sleeper: Mutex<Option<std::thread::Thread>>,
+}
+
+impl Shared {
+    fn schedule(&self, runnable: Runnable) {
+        self.queue.lock().unwrap().push_back(runnable);
+        self.notify();
+    }
+
+    fn notify(&self) {
+        if let Some(thread) = &*self.sleeper.lock().unwrap() {
+            thread.unpark();
+        }
+    }
+}
+
+struct MainWaker {
+    woken: AtomicBool,
+    shared: Arc<Shared>,
+}
+
+impl Wake for MainWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Ordering::SeqCst);
+        self.shared.notify();
+    }
+}
+
+impl Default for WasiExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WasiExecutor {
+    /// Create a new `WasiExecutor`.
+    pub fn new() -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                queue: Mutex::new(VecDeque::new()),
+                sleeper: Mutex::new(None),
+            }),
+            _local: PhantomData,
+        }
+    }
+
+    /// Run a single queued task, returning `false` if there were none.
+    pub fn try_tick(&self) -> bool {
+        let runnable = self.shared.queue.lock().unwrap().pop_front();
+        match runnable {
+            Some(runnable) => {
+                runnable.run();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Run queued tasks until none of them can make progress.
+    pub fn run_until_stalled(&self) {
+        while self.try_tick() {}
+    }
+
+    /// Drive a future to completion, running spawned tasks in the meantime.
+    pub fn block_on<T>(&self, future: impl Future<Output = T>) -> T {
+        let mut future = pin!(future);
+
+        *self.shared.sleeper.lock().unwrap() = Some(std::thread::current());
+
+        let main = Arc::new(MainWaker {
+            woken: AtomicBool::new(true),
+            shared: self.shared.clone(),
+        });
+        let waker = Waker::from(main.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        loop {
+            if main.woken.swap(false, Ordering::SeqCst) {
+                if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                    return output;
+                }
+            }
+
+            if self.try_tick() {
+                continue;
+            }
+
+            if !main.woken.load(Ordering::SeqCst) {
+                #[cfg(not(all(target_family = "wasm", not(target_feature = "atomics"))))]
+                std::thread::park();
+
+                #[cfg(all(target_family = "wasm", not(target_feature = "atomics")))]
+                panic!("deadlock: no tasks can make progress");
+            }
+        }
+    }
+}
+
+impl<F: Future + 'static> Executor<F> for WasiExecutor
+where
+    F::Output: 'static,
+{
+    type Task = Task<F::Output>;
+    type Error = Infallible;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        let shared = self.shared.clone();
+        let (runnable, task) =
+            async_task_crate::spawn_local(future, move |runnable| shared.schedule(runnable));
+        runnable.schedule();
+        Ok(task)
+    }
+}
+
+impl<F: Future + 'static> LocalExecutor<F> for WasiExecutor
+where
+    F::Output: 'static,
+{
+    type Task = Task<F::Output>;
+    type Error = Infallible;
+
+    fn try_spawn_local(&self, future: F) -> Result<Self::Task, Self::Error> {
+        self.try_spawn(future)
+    }
+}
@@ -0,0 +1,107 @@
+//! Spawning handles that do not keep an executor alive.
+
+use crate::Executor;
+
+use alloc::sync::{Arc, Weak};
+
+use core::fmt;
+use core::future::Future;
+
+/// Executor handle that does not keep a shared executor alive.
+///
+/// Spawning fails with [`WeakError::Gone`] once every [`Arc`] pointing to the executor has
+/// been dropped. This is useful for handing a spawner to long-lived tasks without keeping
+/// the runtime alive forever.
+pub struct WeakExecutor<E: ?Sized> {
+    exec: Weak<E>,
+}
+
+impl<E: ?Sized> fmt::Debug for WeakExecutor<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WeakExecutor { .. }")
+    }
+}
+
+impl<E: ?Sized> Clone for WeakExecutor<E> {
+    fn clone(&self) -> Self {
+        Self {
+            exec: self.exec.clone(),
+        }
+    }
+}
+
+impl<E: ?Sized> WeakExecutor<E> {
+    /// Create a weak handle to a shared executor.
+    pub fn new(exec: &Arc<E>) -> Self {
+        Self {
+            exec: Arc::downgrade(exec),
+        }
+    }
+
+    /// Get a strong reference to the executor, if it is still alive.
+    pub fn upgrade(&self) -> Option<Arc<E>> {
+        self.exec.upgrade()
+    }
+}
+
+impl<F: Future, E: Executor<F> + ?Sized> Executor<F> for WeakExecutor<E> {
+    type Task = E::Task;
+    type Error = WeakError<E::Error>;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        let exec = self
+            .upgrade()
+            .ok_or(WeakError::Gone(ExecutorGone { _private: () }))?;
+        exec.try_spawn(future).map_err(WeakError::Spawn)
+    }
+}
+
+/// Error returned when the executor behind a [`WeakExecutor`] has been dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutorGone {
+    _private: (),
+}
+
+impl fmt::Display for ExecutorGone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("executor has been dropped")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ExecutorGone {}
+
+/// Error returned by spawning onto a [`WeakExecutor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeakError<E> {
+    /// The executor has been dropped.
+    Gone(ExecutorGone),
+
+    /// The executor failed to spawn the future.
+    Spawn(E),
+}
+
+impl<E> From<ExecutorGone> for WeakError<E> {
+    fn from(err: ExecutorGone) -> Self {
+        WeakError::Gone(err)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for WeakError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeakError::Gone(err) => fmt::Display::fmt(err, f),
+            WeakError::Spawn(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for WeakError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WeakError::Gone(err) => Some(err),
+            WeakError::Spawn(err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Tests for `AsyncCancellableTask`.
+
+#![cfg(all(feature = "afit", feature = "async-executor"))]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::{block_on, pending};
+use futures_task_lite::AsyncCancellableTask;
+
+#[test]
+fn test_cancel_after_completion() {
+    let ex = LocalExecutor::new();
+    let task = ex.spawn(async { 5 });
+
+    while ex.try_tick() {}
+
+    assert_eq!(
+        block_on(ex.run(AsyncCancellableTask::cancel(task))),
+        Some(5)
+    );
+}
+
+#[test]
+fn test_cancel_before_completion() {
+    let ex = LocalExecutor::new();
+    let task = ex.spawn(pending::<i32>());
+
+    while ex.try_tick() {}
+
+    assert_eq!(block_on(ex.run(AsyncCancellableTask::cancel(task))), None);
+}
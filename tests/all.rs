@@ -0,0 +1,66 @@
+//! Tests for `all`.
+
+#![cfg(all(feature = "ext", feature = "async-executor"))]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::block_on;
+use futures_task_lite::{all, Executor};
+
+use std::cell::Cell;
+use std::future::Future;
+use std::rc::Rc;
+
+struct Batching<'a, 'b> {
+    ex: &'b LocalExecutor<'a>,
+    batches: Cell<usize>,
+}
+
+impl<'a, F: Future + 'a> Executor<F> for Batching<'a, '_> {
+    type Task = <LocalExecutor<'a> as Executor<F>>::Task;
+    type Error = <LocalExecutor<'a> as Executor<F>>::Error;
+
+    fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+        self.ex.try_spawn(future)
+    }
+
+    fn try_spawn_all(&self, futures: Vec<F>) -> Result<Vec<Self::Task>, Self::Error> {
+        self.batches.set(self.batches.get() + 1);
+        futures.into_iter().map(|f| self.ex.try_spawn(f)).collect()
+    }
+}
+
+#[test]
+fn test_all_spawns_in_one_batch() {
+    let ex = LocalExecutor::new();
+    let batching = Batching {
+        ex: &ex,
+        batches: Cell::new(0),
+    };
+
+    block_on(ex.run(async {
+        let futures = (1..=3).map(|x| async move { x });
+        let mut results = Vec::new();
+        all(&batching, futures, &mut results).await.unwrap();
+
+        assert_eq!(results, [1, 2, 3]);
+    }));
+    assert_eq!(batching.batches.get(), 1);
+}
+
+#[test]
+fn test_all_batch_through_rc() {
+    let ex = LocalExecutor::new();
+    let batching = Rc::new(Batching {
+        ex: &ex,
+        batches: Cell::new(0),
+    });
+
+    block_on(ex.run(async {
+        let futures = (1..=3).map(|x| async move { x });
+        let mut results = Vec::new();
+        all(batching.clone(), futures, &mut results).await.unwrap();
+
+        assert_eq!(results, [1, 2, 3]);
+    }));
+    assert_eq!(batching.batches.get(), 1);
+}
@@ -0,0 +1,74 @@
+//! Tests for `all_unordered` and `all_indexed`.
+
+#![cfg(all(feature = "ext", feature = "async-executor"))]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::{block_on, yield_now};
+use futures_lite::StreamExt;
+use futures_task_lite::{all_indexed, all_unordered, all_unordered_limited};
+
+use std::cell::Cell;
+
+#[test]
+fn test_all_unordered() {
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let futures = (1..=3).map(|x| async move { x });
+        let mut results = all_unordered(&ex, futures)
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await;
+
+        results.sort();
+        assert_eq!(results, [1, 2, 3]);
+    }));
+}
+
+#[test]
+fn test_all_unordered_limited() {
+    let count = Cell::new(0);
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let futures = (1..=5).map(|x| {
+            let count = &count;
+            async move {
+                count.set(count.get() + 1);
+                assert!(count.get() <= 2, "count exceeded two at a time");
+                yield_now().await;
+                count.set(count.get() - 1);
+                x
+            }
+        });
+
+        let total = all_unordered_limited(&ex, futures, 2)
+            .map(Result::unwrap)
+            .fold(0, |acc, x| acc + x)
+            .await;
+
+        assert_eq!(total, 15);
+    }));
+}
+
+#[test]
+fn test_all_indexed() {
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let futures = (0..3).map(|x| async move {
+            for _ in 0..(3 - x) {
+                yield_now().await;
+            }
+            x * 10
+        });
+        let mut results = all_indexed(&ex, futures)
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await;
+
+        // Every output is tagged with the index of its future.
+        results.sort();
+        assert_eq!(results, [(0, 0), (1, 10), (2, 20)]);
+    }));
+}
@@ -0,0 +1,48 @@
+//! Tests for `all_weighted`.
+
+#![cfg(all(feature = "ext", feature = "async-executor"))]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::{block_on, yield_now};
+use futures_task_lite::all_weighted;
+
+use std::cell::Cell;
+
+#[test]
+fn test_all_weighted() {
+    let used = Cell::new(0);
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let futures = [3, 1, 2, 5, 1].into_iter().map(|weight| {
+            let used = &used;
+            let future = async move {
+                used.set(used.get() + weight);
+                assert!(used.get() <= 4 || used.get() == weight, "capacity exceeded");
+                yield_now().await;
+                used.set(used.get() - weight);
+                weight
+            };
+            (future, weight)
+        });
+
+        let mut results = Vec::new();
+        all_weighted(&ex, futures, &mut results, 4).await.unwrap();
+        assert_eq!(results, [3, 1, 2, 5, 1]);
+    }));
+}
+
+#[test]
+fn test_all_weighted_huge_weight() {
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        // A weight near `usize::MAX` must not overflow the running total.
+        let futures =
+            [(1, 1), (2, usize::MAX), (3, 1)].map(|(value, weight)| (async move { value }, weight));
+
+        let mut results = Vec::new();
+        all_weighted(&ex, futures, &mut results, 4).await.unwrap();
+        assert_eq!(results, [1, 2, 3]);
+    }));
+}
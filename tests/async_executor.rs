@@ -88,3 +88,38 @@ fn test_all_on_boxed() {
         assert_eq!(results, [1, 2, 3]);
     }));
 }
+
+#[test]
+fn test_try_spawn_all() {
+    use futures_task_lite::Executor as _;
+
+    let ex = Executor::new();
+    let tasks = ex
+        .try_spawn_all(vec![ready(1), ready(2), ready(3)])
+        .unwrap();
+    let results = block_on(ex.run(async {
+        let mut results = Vec::new();
+        for task in tasks {
+            results.push(task.await);
+        }
+        results
+    }));
+    assert_eq!(results, [1, 2, 3]);
+
+    // Local futures do not need to be `Send`.
+    let ex = LocalExecutor::new();
+    let value = std::rc::Rc::new(5);
+    let futures = (0..3).map(|i| {
+        let value = value.clone();
+        async move { *value + i }
+    });
+    let tasks = ex.try_spawn_all(futures.collect()).unwrap();
+    let results = block_on(ex.run(async {
+        let mut results = Vec::new();
+        for task in tasks {
+            results.push(task.await);
+        }
+        results
+    }));
+    assert_eq!(results, [5, 6, 7]);
+}
@@ -0,0 +1,45 @@
+//! Tests for `Balanced`.
+
+#![cfg(feature = "async-executor")]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::block_on;
+use futures_task_lite::adapter::Balanced;
+use futures_task_lite::Executor;
+
+#[test]
+fn test_round_robin() {
+    let (a, b) = (LocalExecutor::new(), LocalExecutor::new());
+    let ex = Balanced::new([&a, &b]);
+
+    let tasks = (0..4)
+        .map(|x| ex.try_spawn(async move { x }).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!((ex.load(0), ex.load(1)), (2, 2));
+
+    // Each inner executor runs its share of the tasks.
+    while a.try_tick() {}
+    assert_eq!((ex.load(0), ex.load(1)), (0, 2));
+    while b.try_tick() {}
+    assert_eq!((ex.load(0), ex.load(1)), (0, 0));
+
+    let outputs = tasks.into_iter().map(block_on).collect::<Vec<_>>();
+    assert_eq!(outputs, [0, 1, 2, 3]);
+}
+
+#[test]
+fn test_least_loaded() {
+    let (a, b) = (LocalExecutor::new(), LocalExecutor::new());
+    let ex = Balanced::least_loaded([&a, &b]);
+
+    let first = ex.try_spawn(async {}).unwrap();
+    let second = ex.try_spawn(async {}).unwrap();
+    assert_eq!((ex.load(0), ex.load(1)), (1, 1));
+
+    // Cancelling a task frees up its executor once the executor drops the future.
+    drop(first);
+    while a.try_tick() {}
+    let _third = ex.try_spawn(async {}).unwrap();
+    assert_eq!((ex.load(0), ex.load(1)), (1, 1));
+    drop(second);
+}
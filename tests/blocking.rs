@@ -0,0 +1,60 @@
+//! Tests for `BlockingExecutor`.
+
+#![cfg(all(
+    feature = "std",
+    not(all(target_family = "wasm", not(target_feature = "atomics")))
+))]
+
+use futures_lite::future::block_on;
+use futures_task_lite::{BlockingExecutor, DetachableTask, NewThread};
+
+use std::sync::mpsc;
+
+#[test]
+fn test_new_thread() {
+    let task = NewThread::default().try_spawn_blocking(|| 1 + 2).unwrap();
+
+    assert_eq!(block_on(task), 3);
+}
+
+#[test]
+fn test_new_thread_dropped() {
+    let (start, started) = mpsc::channel();
+    let (sender, receiver) = mpsc::channel();
+
+    let task = NewThread::default()
+        .try_spawn_blocking(move || {
+            started.recv().unwrap();
+            sender.send(5).unwrap();
+        })
+        .unwrap();
+
+    // Dropping the handle before the closure runs detaches it instead of cancelling it.
+    drop(task);
+    start.send(()).unwrap();
+    assert_eq!(receiver.recv().unwrap(), 5);
+}
+
+#[test]
+fn test_new_thread_detach() {
+    let (sender, receiver) = mpsc::channel();
+
+    NewThread::default()
+        .try_spawn_blocking(move || sender.send(5).unwrap())
+        .unwrap()
+        .detach();
+
+    assert_eq!(receiver.recv().unwrap(), 5);
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn test_blocking_pool() {
+    use futures_task_lite::impls::BlockingPool;
+
+    let task = BlockingPool::default()
+        .try_spawn_blocking(|| 1 + 2)
+        .unwrap();
+
+    assert_eq!(block_on(task), 3);
+}
@@ -0,0 +1,94 @@
+//! Tests for the `Bounded` executor adapter.
+
+#![cfg(feature = "async-executor")]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::{block_on, pending, yield_now};
+use futures_task_lite::adapter::{Bounded, BoundedError};
+use futures_task_lite::Executor;
+
+#[test]
+fn test_bounded_full() {
+    let ex = LocalExecutor::new();
+    let bounded = Bounded::new(&ex, 1);
+
+    block_on(ex.run(async {
+        let task = bounded.try_spawn(pending::<()>()).unwrap();
+        assert_eq!(bounded.in_flight(), 1);
+        assert!(matches!(
+            bounded.try_spawn(pending::<()>()),
+            Err(BoundedError::Full)
+        ));
+
+        // Cancelling the task frees up its slot.
+        drop(task);
+        yield_now().await;
+        assert_eq!(bounded.in_flight(), 0);
+        assert!(bounded.try_spawn(pending::<()>()).is_ok());
+    }));
+}
+
+#[test]
+fn test_bounded_spawn_waits() {
+    let ex = LocalExecutor::new();
+    let bounded = Bounded::new(&ex, 1);
+
+    block_on(ex.run(async {
+        let first = bounded.spawn(async { 1 }).await.unwrap();
+        let second = bounded.spawn(async { 2 }).await.unwrap();
+
+        assert_eq!(first.await + second.await, 3);
+        assert_eq!(bounded.in_flight(), 0);
+    }));
+}
+
+#[test]
+#[should_panic = "limit must be greater than zero"]
+fn test_bounded_zero_limit() {
+    let ex = LocalExecutor::new();
+    Bounded::new(&ex, 0);
+}
+
+#[test]
+fn test_bounded_wakes_one_waiter() {
+    use std::future::Future;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Wake, Waker};
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let ex = LocalExecutor::new();
+    let bounded = Bounded::new(&ex, 1);
+    let first = bounded.try_spawn(pending::<()>()).unwrap();
+
+    let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    let waker = Waker::from(counter.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    // Each waiter holds one place in the queue, however often it is polled.
+    let mut waiters = (0..4)
+        .map(|_| Box::pin(bounded.spawn(pending::<()>())))
+        .collect::<Vec<_>>();
+    for _ in 0..3 {
+        for waiter in &mut waiters {
+            assert!(waiter.as_mut().poll(&mut cx).is_pending());
+        }
+    }
+
+    // Releasing the permit wakes a single waiter.
+    drop(first);
+    block_on(ex.tick());
+    assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+
+    // If that waiter goes away, the wakeup is passed on to the next one.
+    drop(waiters.remove(0));
+    assert_eq!(counter.0.load(Ordering::SeqCst), 2);
+    assert!(waiters[0].as_mut().poll(&mut cx).is_ready());
+}
@@ -0,0 +1,125 @@
+//! Tests for `BoxedExecutor` and `LocalBoxedExecutor`.
+
+#![cfg(feature = "std")]
+
+use futures_lite::future::block_on;
+use futures_task_lite::testing::TestExecutor;
+use futures_task_lite::{ExecutorExt, FutureExt, ThreadExecutor};
+
+use std::rc::Rc;
+
+#[test]
+fn test_boxed_any_output() {
+    let boxed = ThreadExecutor::default().boxed();
+
+    let number = async { 1 + 2 }.try_par(&boxed).unwrap();
+    let string = async { "hello" }.try_par(&boxed).unwrap();
+
+    assert_eq!(block_on(number), 3);
+    assert_eq!(block_on(string), "hello");
+}
+
+#[test]
+fn test_local_boxed_any_output() {
+    let ex = Rc::new(TestExecutor::new());
+    let boxed = ex.clone().boxed_local();
+
+    let number = async { 1 + 2 }.try_par(&boxed).unwrap();
+    let rc = async { Rc::new(5) }.try_par(&boxed).unwrap();
+    ex.run_until_stalled();
+
+    assert_eq!(block_on(number), 3);
+    assert_eq!(*block_on(rc), 5);
+}
+
+#[test]
+fn test_boxed_clone() {
+    let boxed = ThreadExecutor::default().boxed();
+    let cloned = boxed.clone();
+
+    // Clones can be moved into other threads.
+    let task = std::thread::spawn(move || async { 1 + 2 }.try_par(&cloned).unwrap())
+        .join()
+        .unwrap();
+    assert_eq!(block_on(task), 3);
+    drop(boxed);
+}
+
+#[test]
+fn test_boxed_shared() {
+    use futures_task_lite::BoxedExecutor;
+    use std::sync::Arc;
+
+    let ex = Arc::new(ThreadExecutor::default());
+    let boxed = BoxedExecutor::shared(ex.clone());
+    let from: BoxedExecutor<'_> = ex.clone().into();
+
+    let task = async { 1 + 2 }.try_par(&boxed).unwrap();
+    assert_eq!(block_on(task), 3);
+    let task = async { "hello" }.try_par(&from).unwrap();
+    assert_eq!(block_on(task), "hello");
+
+    // Both share the original allocation.
+    assert_eq!(Arc::strong_count(&ex), 3);
+}
+
+#[test]
+fn test_infallible_boxed() {
+    use futures_task_lite::{InfallibleBoxedExecutor, InfallibleExecutor};
+
+    let boxed = InfallibleBoxedExecutor::new(ThreadExecutor::default().infallible());
+    let task = boxed.spawn(async { 1 + 2 });
+    assert_eq!(block_on(task), 3);
+}
+
+#[test]
+fn test_spawn_error_downcast() {
+    use futures_task_lite::testing::TestSpawnError;
+    use futures_task_lite::{Executor, LocalSpawnError};
+
+    let ex = Rc::new(TestExecutor::new());
+    let boxed = ex.clone().boxed_local();
+    ex.fail_next(1);
+
+    let err: LocalSpawnError = match boxed.try_spawn(async {}) {
+        Ok(_) => panic!("spawn should have failed"),
+        Err(err) => err,
+    };
+    assert!(err.is::<TestSpawnError>());
+    assert_eq!(err.to_string(), "injected spawn failure");
+    assert!(err.downcast::<TestSpawnError>().is_ok());
+}
+
+#[test]
+fn test_local_boxed_non_send_error() {
+    use futures_task_lite::adapter::ExecutorFn;
+    use futures_task_lite::Executor;
+
+    use std::fmt;
+    use std::future::Future;
+    use std::marker::PhantomData;
+    use std::pin::Pin;
+
+    /// An error that cannot leave the thread.
+    #[derive(Debug)]
+    struct LocalError(PhantomData<Rc<()>>);
+
+    impl fmt::Display for LocalError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("local error")
+        }
+    }
+
+    impl std::error::Error for LocalError {}
+
+    let failing = ExecutorFn::new(|future: Pin<Box<dyn Future<Output = ()>>>| {
+        drop(future);
+        Err::<Pin<Box<dyn Future<Output = ()>>>, _>(LocalError(PhantomData))
+    });
+    let boxed = failing.boxed_local();
+
+    match boxed.try_spawn(async {}) {
+        Ok(_) => panic!("spawn should have failed"),
+        Err(err) => assert!(err.is::<LocalError>()),
+    }
+}
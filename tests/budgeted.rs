@@ -0,0 +1,38 @@
+//! Tests for the `Budgeted` adapter.
+
+#![cfg(feature = "async-executor")]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::{block_on, poll_fn};
+use futures_task_lite::{Executor, ExecutorExt};
+
+use std::cell::Cell;
+use std::task::Poll;
+
+#[test]
+fn test_budgeted() {
+    let polls = Cell::new(0);
+    let ex = LocalExecutor::new();
+    let budgeted = (&ex).with_budget(3);
+
+    // A future that wakes itself on every poll, and finishes after ten polls.
+    let future = poll_fn(|cx| {
+        polls.set(polls.get() + 1);
+        if polls.get() == 10 {
+            Poll::Ready(())
+        } else {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    });
+    let task = budgeted.try_spawn(future).unwrap();
+
+    // Every fourth poll of the task yields without reaching the future.
+    let mut ticks = 0;
+    while ex.try_tick() {
+        ticks += 1;
+    }
+    assert_eq!(polls.get(), 10);
+    assert_eq!(ticks, 13);
+    block_on(task);
+}
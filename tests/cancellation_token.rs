@@ -0,0 +1,42 @@
+//! Tests for `CancellationToken`.
+
+#![cfg(feature = "std")]
+
+use futures_lite::future::{block_on, pending};
+use futures_task_lite::testing::TestExecutor;
+use futures_task_lite::{CancellationToken, ExecutorExt, ThreadExecutor};
+
+#[test]
+fn test_cancel_all() {
+    let token = CancellationToken::new();
+    let ex = TestExecutor::new();
+    let exec = (&ex).with_token(token.clone());
+
+    let done = exec.try_spawn(async { 5 }).unwrap();
+    let stuck = exec.try_spawn(pending::<()>()).unwrap();
+    ex.run_until_stalled();
+    assert_eq!(ex.len(), 1);
+
+    token.cancel_all();
+    ex.run_until_stalled();
+    assert!(ex.is_empty());
+
+    assert_eq!(block_on(done), Some(5));
+    assert_eq!(block_on(stuck), None);
+}
+
+#[test]
+fn test_across_threads() {
+    let token = CancellationToken::new();
+    let exec = ThreadExecutor::default().with_token(token.clone());
+
+    let tasks = (0..4)
+        .map(|_| exec.try_spawn(pending::<()>()).unwrap())
+        .collect::<Vec<_>>();
+
+    token.cancel_all();
+    for task in tasks {
+        assert_eq!(block_on(task), None);
+    }
+    block_on(token.cancelled());
+}
@@ -0,0 +1,23 @@
+//! Tests for the `CatchUnwind` executor adapter.
+
+#![cfg(feature = "tokio")]
+
+use futures_task_lite::adapter::CatchUnwind;
+
+#[test]
+fn test_catch_unwind() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let ex = CatchUnwind::new(rt.handle().clone());
+
+    let ok = ex.try_spawn(async { 1 }).unwrap();
+    let panicked = ex.try_spawn(async { panic!("oh no") }).unwrap();
+
+    rt.block_on(async {
+        assert_eq!(ok.await.unwrap(), 1);
+
+        let payload = panicked.await.unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"oh no"));
+    });
+}
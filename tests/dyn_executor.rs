@@ -0,0 +1,25 @@
+//! Tests for the object-safe `DynExecutor`.
+
+#![cfg(feature = "async-executor")]
+
+use async_executor_crate::Executor;
+use futures_lite::future::block_on;
+use futures_task_lite::{DynExecutor, FutureExt};
+
+struct Config<'a> {
+    executor: &'a dyn DynExecutor<'static>,
+}
+
+#[test]
+fn test_dyn_executor() {
+    let ex = Executor::new();
+    let config = Config { executor: &ex };
+
+    block_on(ex.run(async {
+        let number = async { 1 + 2 }.try_par(config.executor).unwrap();
+        let string = async { "hello" }.try_par(config.executor).unwrap();
+
+        assert_eq!(number.await, 3);
+        assert_eq!(string.await, "hello");
+    }));
+}
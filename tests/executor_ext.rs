@@ -0,0 +1,35 @@
+//! Tests for `ExecutorExt`.
+
+#![cfg(feature = "tokio")]
+
+use futures_task_lite::impls::TokioGlobal;
+use futures_task_lite::{Executor, ExecutorExt, FutureExt};
+
+#[test]
+fn test_map_err() {
+    // There is no runtime, so spawning fails.
+    let ex = TokioGlobal::default().map_err(|_| "no runtime");
+    let result = ex.try_spawn(async {}).map(drop);
+
+    assert_eq!(result, Err("no runtime"));
+}
+
+#[test]
+#[should_panic = "failed to spawn future"]
+fn test_infallible() {
+    let ex = TokioGlobal::default().infallible();
+    drop(async {}.par(ex));
+}
+
+#[test]
+fn test_or_else() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    // There is no runtime in this context, so spawning falls back to the handle.
+    let ex = TokioGlobal::default().or_else(rt.handle().clone());
+    let task = async { 5 }.par(&ex);
+
+    assert_eq!(rt.block_on(task), 5);
+}
@@ -0,0 +1,37 @@
+//! Tests for `ExecutorFn`.
+
+#![cfg(feature = "async-executor")]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::block_on;
+use futures_task_lite::adapter::ExecutorFn;
+use futures_task_lite::{Executor, FutureExt};
+
+use std::cell::Cell;
+use std::convert::Infallible;
+use std::future::Future;
+
+#[test]
+fn test_executor_fn() {
+    let spawned = Cell::new(0);
+    let ex = LocalExecutor::new();
+
+    let exec = ExecutorFn::new(|future| {
+        spawned.set(spawned.get() + 1);
+        Ok::<_, Infallible>(ex.spawn(future))
+    });
+
+    let task = async { 1 + 2 }.par(&exec);
+    assert_eq!(block_on(ex.run(task)), 3);
+    assert_eq!(spawned.get(), 1);
+}
+
+#[test]
+fn test_executor_fn_error() {
+    fn failing<F: Future>() -> impl Executor<F, Error = &'static str> {
+        ExecutorFn::new(|_| Err::<F, _>("no executor"))
+    }
+
+    let result = failing().try_spawn(async {}).map(drop);
+    assert_eq!(result, Err("no executor"));
+}
@@ -0,0 +1,81 @@
+//! Tests for the `_mut` combinators.
+
+#![cfg(all(feature = "ext", feature = "async-executor"))]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::{block_on, pending, yield_now};
+use futures_task_lite::{all_limited_mut, all_mut, or_mut, ExecutorMut};
+
+use std::convert::Infallible;
+use std::future::Future;
+
+/// A spawner that needs `&mut self`, and so cannot implement `Executor` on its own.
+struct Counting<'a, 'b> {
+    ex: &'b LocalExecutor<'a>,
+    spawned: usize,
+}
+
+impl<'a, F: Future + 'a> ExecutorMut<F> for Counting<'a, '_> {
+    type Task = async_executor_crate::Task<F::Output>;
+    type Error = Infallible;
+
+    fn try_spawn_mut(&mut self, future: F) -> Result<Self::Task, Self::Error> {
+        self.spawned += 1;
+        Ok(self.ex.spawn(future))
+    }
+}
+
+#[test]
+fn test_all_mut() {
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let mut counting = Counting {
+            ex: &ex,
+            spawned: 0,
+        };
+
+        let mut results = Vec::new();
+        all_mut(
+            &mut counting,
+            (0..3).map(|x| async move { x }),
+            &mut results,
+        )
+        .await
+        .unwrap();
+        assert_eq!(results, [0, 1, 2]);
+
+        let mut results = Vec::new();
+        let futures = (0..4).map(|x| async move {
+            yield_now().await;
+            x
+        });
+        all_limited_mut(&mut counting, futures, &mut results, 2)
+            .await
+            .unwrap();
+        assert_eq!(results, [0, 1, 2, 3]);
+
+        assert_eq!(counting.spawned, 7);
+    }));
+}
+
+#[test]
+fn test_or_mut() {
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let mut counting = Counting {
+            ex: &ex,
+            spawned: 0,
+        };
+        let futures = (0..3).map(|x| async move {
+            if x != 0 {
+                pending::<()>().await;
+            }
+            x
+        });
+
+        assert_eq!(or_mut(&mut counting, futures).await.unwrap(), 0);
+        assert_eq!(counting.spawned, 3);
+    }));
+}
@@ -0,0 +1,60 @@
+//! Tests for the C host executor adapter.
+
+#![cfg(feature = "ffi")]
+
+use futures_lite::future::{block_on, yield_now};
+use futures_task_lite::{FutureExt, HostCallback, HostExecutor};
+
+use std::collections::VecDeque;
+use std::ffi::c_void;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Mutex;
+
+type Queue = Mutex<VecDeque<(HostCallback, usize)>>;
+
+unsafe extern "C" fn schedule(ctx: *mut c_void, callback: HostCallback, task: *mut c_void) {
+    let queue = unsafe { &*(ctx as *const Queue) };
+    queue.lock().unwrap().push_back((callback, task as usize));
+}
+
+fn run(queue: &Queue) {
+    loop {
+        let next = queue.lock().unwrap().pop_front();
+        match next {
+            Some((callback, task)) => unsafe { callback(task as *mut c_void) },
+            None => break,
+        }
+    }
+}
+
+#[test]
+fn test_host_executor() {
+    let queue: &'static Queue = Box::leak(Box::new(Mutex::new(VecDeque::new())));
+    let ex = unsafe { HostExecutor::new(schedule, queue as *const Queue as *mut c_void) };
+
+    let task = async {
+        yield_now().await;
+        yield_now().await;
+        42
+    }
+    .par(ex);
+
+    run(queue);
+    assert_eq!(block_on(task), 42);
+}
+
+#[test]
+fn test_panic_stays_out_of_host() {
+    let queue: &'static Queue = Box::leak(Box::new(Mutex::new(VecDeque::new())));
+    let ex = unsafe { HostExecutor::new(schedule, queue as *const Queue as *mut c_void) };
+
+    let task = async {
+        yield_now().await;
+        panic!("boom");
+    }
+    .par(ex);
+
+    // The host loop keeps running; the panic only shows up when the task is awaited.
+    run(queue);
+    assert!(catch_unwind(AssertUnwindSafe(|| block_on(task))).is_err());
+}
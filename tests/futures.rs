@@ -0,0 +1,34 @@
+//! Tests for the `futures` interop layer.
+
+#![cfg(all(feature = "futures", feature = "async-executor"))]
+
+use async_executor_crate::Executor;
+use futures_lite::future::{block_on, pending};
+use futures_task_lite::impls::{ExecutorSpawner, SpawnExecutor};
+use futures_task_lite::{CancellableTask, FutureExt};
+
+#[test]
+fn test_round_trip() {
+    let ex = Executor::new();
+
+    // Convert into a `futures::task::Spawn` and back again.
+    let spawner = SpawnExecutor(ExecutorSpawner(&ex));
+
+    block_on(ex.run(async {
+        let task = async { 1 + 2 }.try_par(&spawner).unwrap();
+        assert_eq!(task.await, 3);
+    }));
+}
+
+#[test]
+fn test_cancel_remote_handle() {
+    let ex = Executor::new();
+    let spawner = SpawnExecutor(ExecutorSpawner(&ex));
+
+    let done = async { 5 }.try_par(&spawner).unwrap();
+    let running = pending::<i32>().try_par(&spawner).unwrap();
+    while ex.try_tick() {}
+
+    assert_eq!(block_on(done.cancel()), Some(5));
+    assert_eq!(block_on(running.cancel()), None);
+}
@@ -0,0 +1,28 @@
+//! Tests for the `futures-executor` implementations.
+
+#![cfg(feature = "futures-executor")]
+
+use futures_crate::executor::{block_on, LocalPool, ThreadPool};
+use futures_task_lite::{FutureExt, LocalExecutor};
+
+#[test]
+fn test_thread_pool() {
+    let pool = ThreadPool::new().unwrap();
+    let task = async { 1 + 2 }.try_par(&pool).unwrap();
+    assert_eq!(block_on(task), 3);
+}
+
+#[test]
+fn test_local_spawner() {
+    let mut pool = LocalPool::new();
+    let spawner = pool.spawner();
+
+    let value = std::rc::Rc::new(5);
+    let task = spawner
+        .try_spawn_local({
+            let value = value.clone();
+            async move { *value * 2 }
+        })
+        .unwrap();
+    assert_eq!(pool.run_until(task), 10);
+}
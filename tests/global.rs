@@ -0,0 +1,22 @@
+//! Tests for the global executor.
+
+#![cfg(feature = "std")]
+
+use futures_lite::future::block_on;
+use futures_task_lite::global::{self, NoGlobalExecutor};
+use futures_task_lite::ThreadExecutor;
+
+// The global executor can only be installed once per process, so everything is tested in order.
+#[test]
+fn test_global_executor() {
+    match global::spawn(async { 1 }) {
+        Ok(_) => panic!("spawned without a global executor"),
+        Err(err) => assert!(err.is::<NoGlobalExecutor>()),
+    }
+
+    global::set_global_executor(ThreadExecutor::default()).unwrap();
+    assert!(global::set_global_executor(ThreadExecutor::default()).is_err());
+
+    let task = global::spawn(async { 1 + 2 }).unwrap();
+    assert_eq!(block_on(task), 3);
+}
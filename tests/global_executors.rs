@@ -0,0 +1,34 @@
+//! Tests for the global executor integrations.
+
+#[cfg(feature = "smol")]
+#[test]
+fn test_smol() {
+    use futures_lite::future::block_on;
+    use futures_task_lite::{impls::SmolGlobal, FutureExt};
+
+    let task = async { 1 + 2 }.par(SmolGlobal::default());
+    assert_eq!(block_on(task), 3);
+}
+
+#[cfg(feature = "async-global-executor")]
+#[test]
+fn test_async_global_executor() {
+    use futures_lite::future::block_on;
+    use futures_task_lite::{impls::AsyncGlobalExecutor, FutureExt};
+
+    let task = async { 1 + 2 }.par(AsyncGlobalExecutor::default());
+    assert_eq!(block_on(task), 3);
+}
+
+#[cfg(feature = "async-std")]
+#[test]
+fn test_async_std() {
+    use futures_lite::future::{block_on, pending};
+    use futures_task_lite::{impls::AsyncStdGlobal, CancellableTask, FutureExt};
+
+    let task = async { 1 + 2 }.try_par(AsyncStdGlobal::default()).unwrap();
+    assert_eq!(block_on(task), 3);
+
+    let task = pending::<i32>().try_par(AsyncStdGlobal::default()).unwrap();
+    assert_eq!(block_on(task.cancel()), None);
+}
@@ -0,0 +1,69 @@
+//! Tests for the `Hooked` executor adapter.
+
+#![cfg(feature = "async-executor")]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::{block_on, pending, yield_now};
+use futures_task_lite::adapter::{Hooked, TaskId};
+use futures_task_lite::FutureExt;
+
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn test_hooks() {
+    let events = Arc::new(Mutex::new(Vec::<(&str, TaskId)>::new()));
+    let ex = LocalExecutor::new();
+
+    let hooked = {
+        let (a, b, c) = (events.clone(), events.clone(), events.clone());
+        Hooked::new(&ex)
+            .on_spawn(move |id| a.lock().unwrap().push(("spawn", id)))
+            .on_complete(move |id| b.lock().unwrap().push(("complete", id)))
+            .on_cancel(move |id| c.lock().unwrap().push(("cancel", id)))
+    };
+
+    block_on(ex.run(async {
+        let done = async { 1 }.par(&hooked);
+        let cancelled = pending::<i32>().par(&hooked);
+
+        assert_eq!(done.await, 1);
+        drop(cancelled);
+        yield_now().await;
+    }));
+
+    let events = events.lock().unwrap();
+    let names = events.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+    assert_eq!(names, ["spawn", "spawn", "complete", "cancel"]);
+
+    // The completion and cancellation belong to the first and second task.
+    assert_eq!(events[2].1, events[0].1);
+    assert_eq!(events[3].1, events[1].1);
+    assert_ne!(events[0].1, events[1].1);
+}
+
+#[test]
+fn test_hooks_spawn_error() {
+    use futures_task_lite::testing::TestExecutor;
+    use futures_task_lite::Executor;
+
+    let events = Arc::new(Mutex::new(Vec::<&str>::new()));
+    let ex = TestExecutor::new();
+
+    let hooked = {
+        let (a, b, c) = (events.clone(), events.clone(), events.clone());
+        Hooked::new(&ex)
+            .on_spawn(move |_| a.lock().unwrap().push("spawn"))
+            .on_complete(move |_| b.lock().unwrap().push("complete"))
+            .on_cancel(move |_| c.lock().unwrap().push("cancel"))
+    };
+
+    // A task that fails to spawn is neither spawned nor cancelled.
+    ex.fail_next(1);
+    assert!(hooked.try_spawn(async { 1 }).is_err());
+    assert!(events.lock().unwrap().is_empty());
+
+    let task = hooked.try_spawn(async { 2 }).unwrap();
+    ex.run_until_stalled();
+    assert_eq!(block_on(task), 2);
+    assert_eq!(*events.lock().unwrap(), ["spawn", "complete"]);
+}
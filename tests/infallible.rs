@@ -0,0 +1,39 @@
+//! Tests for the `_infallible` combinators.
+
+#![cfg(all(feature = "ext", feature = "async-executor"))]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::{block_on, pending, yield_now};
+use futures_task_lite::{all_infallible, all_limited_infallible, or_infallible};
+
+#[test]
+fn test_all_infallible() {
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let mut results = Vec::new();
+        all_infallible(&ex, (0..4).map(|x| async move { x * 2 }), &mut results).await;
+        assert_eq!(results, [0, 2, 4, 6]);
+
+        let mut results = Vec::new();
+        let futures = (0..4).map(|x| async move {
+            yield_now().await;
+            x
+        });
+        all_limited_infallible(&ex, futures, &mut results, 2).await;
+        assert_eq!(results, [0, 1, 2, 3]);
+    }));
+}
+
+#[test]
+fn test_or_infallible() {
+    let ex = LocalExecutor::new();
+    let futures = (0..3).map(|x| async move {
+        if x != 2 {
+            pending::<()>().await;
+        }
+        x
+    });
+
+    assert_eq!(block_on(ex.run(or_infallible(&ex, futures))), 2);
+}
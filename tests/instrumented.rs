@@ -0,0 +1,58 @@
+//! Tests for the `Instrumented` executor adapter.
+
+#![cfg(feature = "async-executor")]
+
+use async_executor_crate::{LocalExecutor, Task};
+use futures_lite::future::{block_on, pending, ready, yield_now};
+use futures_task_lite::adapter::{ExecutorFn, Instrumented, Metrics};
+use futures_task_lite::{Executor, FutureExt};
+
+#[test]
+fn test_metrics() {
+    let ex = LocalExecutor::new();
+    let instrumented = Instrumented::new(&ex);
+
+    block_on(ex.run(async {
+        let done = async { 1 }.par(&instrumented);
+        let cancelled = pending::<i32>().par(&instrumented);
+        let _running = pending::<i32>().par(&instrumented);
+
+        assert_eq!(done.await, 1);
+        drop(cancelled);
+        yield_now().await;
+
+        let metrics = instrumented.metrics();
+        assert_eq!(metrics.spawned, 3);
+        assert_eq!(metrics.running, 1);
+        assert_eq!(metrics.completed, 1);
+        assert_eq!(metrics.cancelled, 1);
+    }));
+}
+
+#[test]
+fn test_spawn_error() {
+    let failing = ExecutorFn::new(|future| {
+        drop(future);
+        Err::<Task<i32>, _>("no executor")
+    });
+    let instrumented = Instrumented::new(failing);
+
+    assert!(instrumented.try_spawn(async { 1 }).is_err());
+    assert_eq!(instrumented.metrics(), Metrics::default());
+}
+
+#[test]
+fn test_inline_completion() {
+    // An executor that runs each future to completion before returning.
+    let inline = ExecutorFn::new(|future| Ok::<_, ()>(ready(block_on(future))));
+    let instrumented = Instrumented::new(inline);
+
+    let task = instrumented.try_spawn(async { 1 }).unwrap();
+
+    let metrics = instrumented.metrics();
+    assert_eq!(metrics.spawned, 1);
+    assert_eq!(metrics.running, 0);
+    assert_eq!(metrics.completed, 1);
+    assert_eq!(metrics.cancelled, 0);
+    assert_eq!(block_on(task), 1);
+}
@@ -0,0 +1,56 @@
+//! Tests for the fixed-arity combinators.
+
+#![cfg(feature = "async-executor")]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::{block_on, pending, yield_now};
+use futures_task_lite::{all_fixed, all_limited_fixed, join2, join3, or2, UnwrapInfallible};
+
+#[test]
+fn test_join() {
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let (a, b) = join2(&ex, async { 1 }, async {
+            yield_now().await;
+            "two"
+        })
+        .await
+        .unwrap();
+        assert_eq!((a, b), (1, "two"));
+
+        let (a, b, c) = join3(&ex, async { 1 }, async { 2 }, async { 3 })
+            .await
+            .unwrap();
+        assert_eq!((a, b, c), (1, 2, 3));
+    }));
+}
+
+#[test]
+fn test_or() {
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let winner = or2(&ex, pending(), async { 2 }).await.unwrap();
+        assert_eq!(winner, 2);
+    }));
+}
+
+#[test]
+fn test_all_fixed() {
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let futures = [3, 1, 2].map(|x| async move {
+            for _ in 0..x {
+                yield_now().await;
+            }
+            x
+        });
+        assert_eq!(all_fixed(&ex, futures).await.unwrap_infallible(), [3, 1, 2]);
+
+        let futures = [1, 2, 3, 4].map(|x| async move { x * 2 });
+        let outputs = all_limited_fixed(&ex, futures, 2).await.unwrap();
+        assert_eq!(outputs, [2, 4, 6, 8]);
+    }));
+}
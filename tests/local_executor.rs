@@ -0,0 +1,42 @@
+//! Tests for `LocalExecutor`.
+
+#![cfg(feature = "async-executor")]
+
+use futures_lite::future::{block_on, yield_now};
+use futures_task_lite::{FutureExt, LocalExecutor};
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[test]
+fn test_spawn_local() {
+    let ex = async_executor_crate::LocalExecutor::new();
+    let count = Rc::new(Cell::new(0));
+
+    let task = ex
+        .try_spawn_local({
+            let count = count.clone();
+            async move {
+                yield_now().await;
+                count.set(count.get() + 1);
+                count
+            }
+        })
+        .unwrap();
+
+    let count = block_on(ex.run(task));
+    assert_eq!(count.get(), 1);
+}
+
+#[test]
+fn test_par_local() {
+    let ex = async_executor_crate::LocalExecutor::new();
+    let value = Rc::new(3);
+
+    let task = {
+        let value = value.clone();
+        async move { *value * 2 }.par_local(&ex)
+    };
+
+    assert_eq!(block_on(ex.run(task)), 6);
+}
@@ -0,0 +1,125 @@
+//! Tests for `or`.
+
+#![cfg(all(feature = "ext", feature = "async-executor"))]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::{block_on, pending, yield_now};
+use futures_task_lite::{or, AnyExecutor};
+
+use std::cell::Cell;
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+
+struct SetOnDrop<'a>(&'a Cell<usize>);
+
+impl Drop for SetOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[test]
+fn test_or_cancels_losers() {
+    let dropped = Cell::new(0);
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let loser = || -> Pin<Box<dyn Future<Output = i32> + '_>> {
+            Box::pin(async {
+                let _guard = SetOnDrop(&dropped);
+                pending::<()>().await;
+                unreachable!()
+            })
+        };
+        let winner = Box::pin(async {
+            yield_now().await;
+            1
+        });
+
+        let futures = [loser(), winner, loser()];
+        assert_eq!(or(&ex, futures).await.unwrap(), 1);
+        assert_eq!(dropped.get(), 2);
+    }));
+}
+
+#[test]
+fn test_or_late_completions() {
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        // Every future completes on its first poll, so the losers finish after the winner.
+        let futures = (0..8).map(|x| async move { x });
+        let result = or(&ex, futures).await.unwrap();
+
+        assert!((0..8).contains(&result));
+    }));
+}
+
+#[test]
+fn test_or_generic() {
+    async fn race<E, F>(exec: E, futures: Vec<F>) -> F::Output
+    where
+        E: AnyExecutor<F>,
+        E::Error: Debug,
+        F: Future,
+    {
+        or(exec, futures).await.unwrap()
+    }
+
+    let ex = LocalExecutor::new();
+    let futures = (0..3).map(|x| async move {
+        if x != 1 {
+            pending::<()>().await;
+        }
+        x
+    });
+    let result = block_on(ex.run(race(&ex, futures.collect())));
+
+    assert_eq!(result, 1);
+}
+
+#[test]
+fn test_or_drops_late_outputs() {
+    let dropped = Cell::new(0);
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let futures = (0..8).map(|_| async { SetOnDrop(&dropped) });
+        drop(or(&ex, futures).await.unwrap());
+    }));
+
+    // The winning output and every late output are dropped exactly once.
+    assert_eq!(dropped.get(), 8);
+}
+
+#[test]
+#[should_panic = "or() requires at least one future"]
+fn test_or_empty() {
+    let ex = LocalExecutor::new();
+    let futures = std::iter::empty::<std::future::Ready<()>>();
+
+    block_on(ex.run(or(&ex, futures))).unwrap();
+}
+
+#[test]
+#[should_panic = "the executor dropped every task spawned by or() before one completed"]
+fn test_or_all_dropped() {
+    use futures_task_lite::{remote_pair, Executor, RemoteTask};
+
+    /// An executor that drops every future it is given.
+    struct Dropping;
+
+    impl<F: Future> Executor<F> for Dropping {
+        type Task = RemoteTask<F::Output>;
+        type Error = ();
+
+        fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+            let (task, remote) = remote_pair(future);
+            drop(remote);
+            Ok(task)
+        }
+    }
+
+    block_on(or(&Dropping, [pending::<()>(), pending()])).unwrap();
+}
@@ -0,0 +1,39 @@
+//! Tests for `par_map_ordered`.
+
+#![cfg(all(feature = "ext", feature = "async-executor"))]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::{block_on, yield_now};
+use futures_lite::StreamExt;
+use futures_task_lite::par_map_ordered;
+
+use std::cell::Cell;
+
+#[test]
+fn test_par_map_ordered() {
+    let count = Cell::new(0);
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        // Later futures finish first, but outputs still come out in input order.
+        let futures = (1..=5).map(|x| {
+            let count = &count;
+            async move {
+                count.set(count.get() + 1);
+                assert!(count.get() <= 3, "count exceeded three at a time");
+                for _ in 0..(10 - x) {
+                    yield_now().await;
+                }
+                count.set(count.get() - 1);
+                x
+            }
+        });
+
+        let results = par_map_ordered(&ex, futures, 3)
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(results, [1, 2, 3, 4, 5]);
+    }));
+}
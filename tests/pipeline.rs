@@ -0,0 +1,37 @@
+//! Tests for `pipeline`.
+
+#![cfg(all(feature = "ext", feature = "async-executor"))]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::{block_on, yield_now};
+use futures_lite::StreamExt;
+use futures_task_lite::pipeline;
+
+use std::cell::Cell;
+use std::convert::Infallible;
+
+#[test]
+fn test_pipeline() {
+    let parsing = Cell::new(0);
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let results = pipeline::<_, Infallible>(["1", "2", "3", "4", "5"])
+            .stage(&ex, 2, |s: &str| {
+                let parsing = &parsing;
+                async move {
+                    parsing.set(parsing.get() + 1);
+                    assert!(parsing.get() <= 2, "count exceeded two at a time");
+                    yield_now().await;
+                    parsing.set(parsing.get() - 1);
+                    s.parse::<i32>().unwrap()
+                }
+            })
+            .stage(&ex, 3, |x| async move { x * 10 })
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(results, [10, 20, 30, 40, 50]);
+    }));
+}
@@ -0,0 +1,76 @@
+//! Tests for `PoolExecutor`.
+
+#![cfg(all(
+    feature = "std",
+    not(all(target_family = "wasm", not(target_feature = "atomics")))
+))]
+
+use futures_lite::future::{block_on, pending};
+use futures_task_lite::{
+    BlockingExecutor, CancellableTask, FutureExt, InfallibleExecutor, PoolExecutor, PoolJob,
+};
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+/// A "pool" that counts its jobs and runs each on a new thread.
+fn counting_pool(jobs: Arc<AtomicUsize>) -> PoolExecutor<impl Fn(PoolJob)> {
+    PoolExecutor::new(move |job: PoolJob| {
+        jobs.fetch_add(1, Ordering::SeqCst);
+        thread::spawn(job);
+    })
+}
+
+#[test]
+fn test_spawn_future() {
+    let jobs = Arc::new(AtomicUsize::new(0));
+    let pool = counting_pool(jobs.clone());
+
+    let task = async { 1 + 2 }.par(&pool);
+    assert_eq!(block_on(task), 3);
+    assert_eq!(jobs.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_spawn_compute() {
+    let jobs = Arc::new(AtomicUsize::new(0));
+    let pool = counting_pool(jobs.clone());
+
+    let sum = pool.spawn_compute(|| (1..=100).sum::<u32>());
+    let product = pool
+        .try_spawn_blocking(|| (1..=5).product::<u32>())
+        .unwrap();
+
+    assert_eq!(block_on(sum), 5050);
+    assert_eq!(block_on(product), 120);
+    assert_eq!(jobs.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_cancel() {
+    let pool = counting_pool(Arc::default());
+
+    // Cancelling wakes the pool thread so that it stops blocking on the future.
+    let task = pool.spawn(pending::<()>());
+    assert_eq!(block_on(task.cancel()), None);
+}
+
+#[test]
+fn test_spawn_compute_dropped() {
+    // A "pool" that holds on to its jobs until they are run by hand.
+    let queue = Arc::new(Mutex::new(Vec::<PoolJob>::new()));
+    let pool = PoolExecutor::new({
+        let queue = queue.clone();
+        move |job| queue.lock().unwrap().push(job)
+    });
+
+    let (sender, receiver) = mpsc::channel();
+    drop(pool.spawn_compute(move || sender.send(5).unwrap()));
+
+    // The task was dropped before the job started, but the closure still runs.
+    for job in queue.lock().unwrap().drain(..) {
+        job();
+    }
+    assert_eq!(receiver.try_recv(), Ok(5));
+}
@@ -0,0 +1,32 @@
+//! Tests for priority-aware spawning.
+
+#![cfg(feature = "priority")]
+
+use futures_lite::future::block_on;
+use futures_task_lite::{ExecutorExt, FutureExt, PrioritizedExecutor, Priority, PriorityExecutor};
+
+use std::sync::Mutex;
+
+#[test]
+fn test_priority_order() {
+    let order = Mutex::new(Vec::new());
+    let ex = PrioritizedExecutor::new();
+
+    let low = ex
+        .try_spawn_with_priority(async { order.lock().unwrap().push("low") }, Priority::Low)
+        .unwrap();
+    let normal = async { order.lock().unwrap().push("normal") }
+        .try_par(&ex)
+        .unwrap();
+    let high = async { order.lock().unwrap().push("high") }
+        .try_par((&ex).with_priority(Priority::High))
+        .unwrap();
+
+    block_on(ex.run(async {
+        low.await;
+        normal.await;
+        high.await;
+    }));
+
+    assert_eq!(*order.lock().unwrap(), ["high", "normal", "low"]);
+}
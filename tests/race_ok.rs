@@ -0,0 +1,59 @@
+//! Tests for `race_ok`.
+
+#![cfg(all(feature = "ext", feature = "async-executor"))]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::{block_on, yield_now};
+use futures_task_lite::race_ok;
+
+#[test]
+fn test_race_ok_first_success() {
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let futures = [0, 1, 2].map(|x| async move {
+            match x {
+                0 => Err("failed"),
+                1 => Ok(x),
+
+                // Never finishes unless cancelled.
+                _ => loop {
+                    yield_now().await;
+                },
+            }
+        });
+        let result = race_ok(&ex, futures).await.unwrap();
+
+        assert_eq!(result, Ok(1));
+    }));
+}
+
+#[test]
+fn test_race_ok_all_failed() {
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let futures = [1, 2, 3].map(|x| async move { Err::<(), _>(x) });
+        let result = race_ok(&ex, futures).await.unwrap();
+
+        assert_eq!(result, Err(vec![1, 2, 3]));
+    }));
+}
+
+#[test]
+fn test_race_ok_pinned_tasks() {
+    use futures_task_lite::adapter::ExecutorFn;
+
+    // An executor whose "tasks" are the futures themselves, which are not `Unpin`.
+    let inline = ExecutorFn::new(Ok::<_, ()>);
+    let futures = (0..3).map(|i| async move {
+        yield_now().await;
+        if i == 2 {
+            Ok(i)
+        } else {
+            Err(i)
+        }
+    });
+
+    assert_eq!(block_on(race_ok(inline, futures)), Ok(Ok(2)));
+}
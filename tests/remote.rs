@@ -0,0 +1,114 @@
+//! Tests for `remote_pair`.
+
+#![cfg(feature = "std")]
+
+use futures_lite::future::{block_on, pending};
+use futures_task_lite::{
+    remote_pair, CancellableTask, DetachableTask, Executor, InspectableTask, RemoteExecutor,
+    RemoteJob,
+};
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[test]
+fn test_remote_output() {
+    let (task, remote) = remote_pair(async { 1 + 2 });
+    thread::spawn(move || block_on(remote));
+
+    assert_eq!(block_on(task), 3);
+}
+
+#[test]
+fn test_remote_cancel() {
+    let (task, remote) = remote_pair(pending::<()>());
+    let handle = thread::spawn(move || block_on(remote));
+
+    // Cancelling wakes the remote future so that it stops.
+    assert_eq!(block_on(task.cancel()), None);
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_remote_detach() {
+    let ran = Arc::new(AtomicBool::new(false));
+    let (task, remote) = remote_pair({
+        let ran = ran.clone();
+        async move { ran.store(true, Ordering::SeqCst) }
+    });
+
+    task.detach();
+    block_on(remote);
+    assert!(ran.load(Ordering::SeqCst));
+}
+
+#[test]
+fn test_remote_cancel_after_completion() {
+    let (task, remote) = remote_pair(async { 5 });
+
+    assert!(!task.is_finished());
+    block_on(remote);
+    assert!(task.is_finished());
+
+    assert_eq!(block_on(task.cancel()), Some(5));
+}
+
+#[test]
+#[should_panic = "remote future was dropped before completing"]
+fn test_remote_dropped() {
+    let (task, remote) = remote_pair(pending::<()>());
+    drop(remote);
+
+    block_on(task);
+}
+
+#[test]
+fn test_remote_output_freed() {
+    let value = Arc::new(());
+    let (task, remote) = remote_pair({
+        let value = value.clone();
+        async move { value }
+    });
+
+    // The output is dropped along with the shared state if it is never taken.
+    block_on(remote);
+    drop(task);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
+
+#[test]
+fn test_remote_many_threads() {
+    for i in 0..100 {
+        let (task, remote) = remote_pair(async move { i });
+        thread::spawn(move || block_on(remote));
+        assert_eq!(block_on(task), i);
+    }
+}
+
+#[test]
+fn test_remote_executor() {
+    // A fixed-size "task pool" that holds its jobs until they are run by hand.
+    let jobs = Arc::new(Mutex::new(Vec::<RemoteJob>::new()));
+    let exec = RemoteExecutor::new({
+        let jobs = jobs.clone();
+        move |job| {
+            let mut jobs = jobs.lock().unwrap();
+            if jobs.len() == 2 {
+                return Err("pool is full");
+            }
+            jobs.push(job);
+            Ok(())
+        }
+    });
+
+    let first = exec.try_spawn(async { 1 }).unwrap();
+    let second = exec.try_spawn(async { 2 }).unwrap();
+    assert_eq!(exec.try_spawn(async { 3 }).unwrap_err(), "pool is full");
+
+    for job in jobs.lock().unwrap().drain(..) {
+        block_on(job);
+    }
+    assert_eq!(block_on(first), 1);
+    assert_eq!(block_on(second), 2);
+}
@@ -0,0 +1,41 @@
+//! Tests for `ScheduledExecutor`.
+
+#![cfg(feature = "async-io")]
+
+use futures_lite::future::block_on;
+use futures_task_lite::adapter::ScheduledExecutor;
+use futures_task_lite::impls::AsyncIoTimer;
+use futures_task_lite::{CancellableTask, ThreadExecutor};
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_spawn_after() {
+    let exec = ScheduledExecutor::new(ThreadExecutor::default(), AsyncIoTimer::default());
+
+    let start = Instant::now();
+    let task = exec
+        .spawn_after(Duration::from_millis(10), async { 5 })
+        .unwrap();
+
+    assert_eq!(block_on(task), 5);
+    assert!(start.elapsed() >= Duration::from_millis(10));
+}
+
+#[test]
+fn test_cancel_before_start() {
+    let exec = ScheduledExecutor::new(ThreadExecutor::default(), AsyncIoTimer::default());
+    let started = Arc::new(AtomicBool::new(false));
+
+    let task = exec
+        .spawn_at(Instant::now() + Duration::from_secs(60), {
+            let started = started.clone();
+            async move { started.store(true, Ordering::SeqCst) }
+        })
+        .unwrap();
+
+    assert_eq!(block_on(task.cancel()), None);
+    assert!(!started.load(Ordering::SeqCst));
+}
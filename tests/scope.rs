@@ -0,0 +1,133 @@
+//! Tests for structured concurrency scopes.
+
+#![cfg(feature = "async-executor")]
+
+use async_executor_crate::{Executor, LocalExecutor};
+use futures_lite::future::{block_on, yield_now};
+use futures_task_lite::scope::{scope, scope_send};
+use futures_task_lite::ThreadExecutor;
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn test_scope_waits_for_tasks() {
+    let counter = Cell::new(0);
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let result = scope(&ex, |s| {
+            Box::pin(async {
+                for _ in 0..3 {
+                    s.spawn(async {
+                        yield_now().await;
+                        counter.set(counter.get() + 1);
+                    })
+                    .unwrap();
+                }
+
+                let task = s.spawn(async { 5 }).unwrap();
+                task.await
+            })
+        })
+        .await;
+
+        assert_eq!(result, Some(5));
+        assert_eq!(counter.get(), 3);
+    }));
+}
+
+#[test]
+fn test_scope_cancel_all() {
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        scope(&ex, |s| {
+            Box::pin(async {
+                s.spawn(async {
+                    loop {
+                        yield_now().await;
+                    }
+                })
+                .unwrap();
+
+                // Without cancellation, the scope would never resolve.
+                s.cancel_all().await;
+            })
+        })
+        .await;
+    }));
+}
+
+#[test]
+fn test_scoped_task_after_cancel_all() {
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let result = scope(&ex, |s| {
+            Box::pin(async {
+                let task = s
+                    .spawn(async {
+                        loop {
+                            yield_now().await;
+                        }
+                    })
+                    .unwrap();
+
+                s.cancel_all().await;
+                task.await
+            })
+        })
+        .await;
+
+        assert_eq!(result, None::<()>);
+    }));
+}
+
+#[test]
+fn test_scope_send() {
+    let counter = AtomicUsize::new(0);
+    let ex = Executor::new();
+
+    let outer = scope_send(&ex, |s| {
+        Box::pin(async {
+            for _ in 0..3 {
+                s.spawn(async {
+                    yield_now().await;
+                    counter.fetch_add(1, Ordering::SeqCst);
+                })
+                .unwrap();
+            }
+
+            // Scopes can be nested inside tasks on a multi-threaded executor.
+            let inner = scope_send(ThreadExecutor::default(), |s| {
+                Box::pin(async { s.spawn(async { 5 }).unwrap().await })
+            });
+            s.spawn(inner).unwrap().await.flatten()
+        })
+    });
+
+    let result = block_on(ex.run(outer));
+    assert_eq!(result, Some(5));
+    assert_eq!(counter.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_scope_send_cancel_all() {
+    let ex = Executor::new();
+
+    block_on(ex.run(scope_send(&ex, |s| {
+        Box::pin(async {
+            let task = s
+                .spawn(async {
+                    loop {
+                        yield_now().await;
+                    }
+                })
+                .unwrap();
+
+            s.cancel_all().await;
+            assert_eq!(task.await, None::<()>);
+        })
+    })));
+}
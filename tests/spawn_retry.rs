@@ -0,0 +1,80 @@
+//! Tests for `spawn_retry`.
+
+#![cfg(feature = "ext")]
+
+use futures_lite::future::block_on;
+use futures_task_lite::{spawn_retry, RetryPolicy, ThreadExecutor, Timer};
+
+use std::cell::RefCell;
+use std::future::{ready, Ready};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Timer that records the delays it is asked for, without sleeping.
+#[derive(Default)]
+struct RecordingTimer {
+    delays: RefCell<Vec<Duration>>,
+}
+
+impl Timer for RecordingTimer {
+    type Sleep = Ready<()>;
+
+    fn sleep_until(&self, _deadline: Instant) -> Self::Sleep {
+        ready(())
+    }
+
+    fn sleep(&self, duration: Duration) -> Self::Sleep {
+        self.delays.borrow_mut().push(duration);
+        ready(())
+    }
+}
+
+#[test]
+fn test_retry_until_success() {
+    let attempts = AtomicUsize::new(0);
+    let timer = RecordingTimer::default();
+    let policy = RetryPolicy::new(&timer)
+        .delay(Duration::from_millis(10))
+        .backoff_factor(2);
+
+    let result = block_on(spawn_retry(
+        ThreadExecutor::default(),
+        || {
+            let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if attempt < 2 {
+                    Err(attempt)
+                } else {
+                    Ok(attempt)
+                }
+            }
+        },
+        policy,
+    ))
+    .unwrap();
+
+    assert_eq!(result, Ok(2));
+    assert_eq!(
+        *timer.delays.borrow(),
+        [Duration::from_millis(10), Duration::from_millis(20)]
+    );
+}
+
+#[test]
+fn test_retries_exhausted() {
+    let attempts = AtomicUsize::new(0);
+    let timer = RecordingTimer::default();
+
+    let result = block_on(spawn_retry(
+        ThreadExecutor::default(),
+        || {
+            let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+            async move { Err::<(), _>(attempt) }
+        },
+        RetryPolicy::new(&timer).max_retries(1),
+    ))
+    .unwrap();
+
+    assert_eq!(result, Err(1));
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,57 @@
+//! Tests for the stream combinators.
+
+#![cfg(feature = "stream")]
+
+use futures_lite::future::block_on;
+use futures_lite::{stream, StreamExt};
+use futures_task_lite::stream::{for_each_concurrent, par_map};
+use futures_task_lite::ThreadExecutor;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[test]
+fn test_par_map_preserves_order() {
+    let outputs = block_on(
+        par_map(
+            ThreadExecutor::default(),
+            stream::iter(0..8u64),
+            3,
+            |i| async move {
+                // Later items finish first.
+                std::thread::sleep(Duration::from_millis(8 - i));
+                i * 2
+            },
+        )
+        .map(Result::unwrap)
+        .collect::<Vec<_>>(),
+    );
+
+    assert_eq!(outputs, [0, 2, 4, 6, 8, 10, 12, 14]);
+}
+
+#[test]
+fn test_for_each_concurrent_limit() {
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+
+    block_on(for_each_concurrent(
+        ThreadExecutor::default(),
+        stream::iter(0..10),
+        2,
+        |_| {
+            let (running, peak) = (running.clone(), peak.clone());
+            async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                std::thread::sleep(Duration::from_millis(2));
+                running.fetch_sub(1, Ordering::SeqCst);
+            }
+        },
+    ))
+    .unwrap();
+
+    assert_eq!(running.load(Ordering::SeqCst), 0);
+    assert!(peak.load(Ordering::SeqCst) <= 2);
+}
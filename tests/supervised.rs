@@ -0,0 +1,56 @@
+//! Tests for the `Supervised` adapter.
+
+#![cfg(feature = "std")]
+
+use futures_lite::future::block_on;
+use futures_task_lite::adapter::{Supervised, SupervisorError};
+use futures_task_lite::testing::TestExecutor;
+use futures_task_lite::ThreadExecutor;
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[test]
+fn test_restart_after_panic() {
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let exec = Supervised::new(ThreadExecutor::default());
+
+    let task = exec
+        .try_spawn({
+            let attempts = attempts.clone();
+            move || {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if attempt < 2 {
+                        panic!("attempt {} failed", attempt);
+                    }
+                    attempt
+                }
+            }
+        })
+        .unwrap();
+
+    assert_eq!(block_on(task).unwrap(), 2);
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_restart_limit() {
+    let backoffs = Rc::new(Cell::new(0));
+    let ex = Rc::new(TestExecutor::new());
+    let exec = Supervised::new(ex.clone()).max_restarts(2).backoff({
+        let backoffs = backoffs.clone();
+        move |_| {
+            backoffs.set(backoffs.get() + 1);
+            async {}
+        }
+    });
+
+    let task = exec.try_spawn(|| async { panic!("always fails") }).unwrap();
+    ex.run_until_stalled();
+
+    assert_eq!(block_on(task), Err::<(), _>(SupervisorError::RestartLimit));
+    assert_eq!(backoffs.get(), 2);
+}
@@ -0,0 +1,60 @@
+//! Tests for `TaskExt`.
+
+#![cfg(feature = "async-executor")]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::block_on;
+use futures_task_lite::{CancellableTask, FutureExt, InspectableTask, TaskExt};
+
+use std::cell::Cell;
+
+#[test]
+fn test_detach_on_drop() {
+    let ran = Cell::new(false);
+    let ex = LocalExecutor::new();
+
+    // Dropping the handle detaches the task, so it still runs.
+    drop(async { ran.set(true) }.par_local(&ex).detach_on_drop());
+    while ex.try_tick() {}
+    assert!(ran.get());
+
+    // The handle can still be awaited.
+    let task = async { 5 }.par_local(&ex).detach_on_drop();
+    assert_eq!(block_on(ex.run(task)), 5);
+}
+
+#[test]
+fn test_map() {
+    let ex = LocalExecutor::new();
+
+    let task = async { 5 }.par_local(&ex).map(|x| x * 2);
+    assert_eq!(block_on(ex.run(task)), 10);
+
+    // Cancelling maps the output if the task had already completed.
+    let task = async { 5 }.par_local(&ex).map(|x| x * 2);
+    while ex.try_tick() {}
+    assert_eq!(block_on(task.cancel()), Some(10));
+}
+
+#[test]
+fn test_is_finished() {
+    let ex = LocalExecutor::new();
+
+    let task = async {}.par_local(&ex).map(drop);
+    assert!(!task.is_finished());
+    while ex.try_tick() {}
+    assert!(task.is_finished());
+}
+
+#[test]
+fn test_par_detach() {
+    let ran = Cell::new(0);
+    let ex = LocalExecutor::new();
+
+    async { ran.set(ran.get() + 1) }.par_detach(&ex);
+    async { ran.set(ran.get() + 1) }
+        .try_par_detach(&ex)
+        .unwrap();
+    while ex.try_tick() {}
+    assert_eq!(ran.get(), 2);
+}
@@ -0,0 +1,67 @@
+//! Tests for `TaskMap`.
+
+#![cfg(feature = "ext")]
+
+use futures_lite::future::{block_on, pending};
+use futures_task_lite::testing::TestExecutor;
+use futures_task_lite::TaskMap;
+
+use std::future::Future;
+use std::pin::Pin;
+
+type BoxFuture = Pin<Box<dyn Future<Output = &'static str>>>;
+
+#[test]
+fn test_insert_replaces() {
+    let ex = TestExecutor::new();
+    let mut map = TaskMap::<_, _, BoxFuture>::new(&ex);
+
+    map.insert("a", Box::pin(pending())).unwrap();
+    map.insert("b", Box::pin(async { "b" })).unwrap();
+    ex.run_until_stalled();
+    assert_eq!(ex.len(), 1);
+
+    // Replacing the pending task cancels it.
+    map.insert("a", Box::pin(async { "a" })).unwrap();
+    assert_eq!(map.len(), 2);
+    ex.run_until_stalled();
+    assert!(ex.is_empty());
+
+    let mut outputs = vec![block_on(map.next()).unwrap(), block_on(map.next()).unwrap()];
+    outputs.sort();
+    assert_eq!(outputs, [("a", "a"), ("b", "b")]);
+    assert_eq!(block_on(map.next()), None);
+}
+
+#[test]
+fn test_remove() {
+    let ex = TestExecutor::new();
+    let mut map = TaskMap::<_, _, BoxFuture>::new(&ex);
+
+    map.insert(1, Box::pin(pending())).unwrap();
+    assert!(map.contains(&1));
+
+    assert!(map.remove(&1));
+    assert!(!map.remove(&1));
+    assert!(map.is_empty());
+
+    ex.run_until_stalled();
+    assert!(ex.is_empty());
+}
+
+#[test]
+fn test_reinsert_after_completion() {
+    let ex = TestExecutor::new();
+    let mut map = TaskMap::<_, _, BoxFuture>::new(&ex);
+
+    for round in ["first", "second"] {
+        map.insert("a", Box::pin(async move { round })).unwrap();
+        map.insert("b", Box::pin(pending())).unwrap();
+        ex.run_until_stalled();
+
+        assert_eq!(block_on(map.next()), Some(("a", round)));
+        assert!(map.contains(&"b"));
+        assert!(!map.contains(&"a"));
+    }
+    assert_eq!(map.len(), 1);
+}
@@ -0,0 +1,127 @@
+//! Tests for `TaskSet`.
+
+#![cfg(all(feature = "ext", feature = "async-executor"))]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::{block_on, yield_now};
+use futures_task_lite::{ExecutorMut, TaskSet};
+
+use std::cell::RefCell;
+use std::future::{ready, Future};
+
+#[test]
+fn test_completion_order() {
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let mut set = TaskSet::new(&ex);
+        for forever in [true, false] {
+            set.spawn(Box::pin(async move {
+                if forever {
+                    loop {
+                        yield_now().await;
+                    }
+                }
+                forever
+            }))
+            .unwrap();
+        }
+        assert_eq!(set.len(), 2);
+
+        assert_eq!(set.next().await, Some(false));
+        assert_eq!(set.len(), 1);
+
+        set.abort_all();
+        assert!(set.is_empty());
+        assert_eq!(set.next().await, None);
+    }));
+}
+
+#[test]
+fn test_executor_mut() {
+    // A spawner that needs `&mut self`, and so cannot implement `Executor` on its own.
+    struct Counting<'a, 'b> {
+        ex: &'b LocalExecutor<'a>,
+        spawned: usize,
+    }
+
+    impl<'a, F: Future + 'a> ExecutorMut<F> for Counting<'a, '_> {
+        type Task = async_executor_crate::Task<F::Output>;
+        type Error = std::convert::Infallible;
+
+        fn try_spawn_mut(&mut self, future: F) -> Result<Self::Task, Self::Error> {
+            self.spawned += 1;
+            Ok(self.ex.spawn(future))
+        }
+    }
+
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let counting = RefCell::new(Counting {
+            ex: &ex,
+            spawned: 0,
+        });
+        let mut set = TaskSet::new(&counting);
+        set.spawn(ready(1)).unwrap();
+        set.spawn(ready(2)).unwrap();
+        assert_eq!(counting.borrow().spawned, 2);
+
+        let mut total = 0;
+        while let Some(x) = set.next().await {
+            total += x;
+        }
+        assert_eq!(total, 3);
+    }));
+}
+
+#[test]
+fn test_only_woken_tasks_are_polled() {
+    use futures_lite::future::pending;
+    use futures_task_lite::adapter::ExecutorFn;
+    use std::cell::Cell;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    type BoxFuture = Pin<Box<dyn Future<Output = usize>>>;
+
+    /// A "task" that counts how often it is polled.
+    struct Counted<'a> {
+        future: BoxFuture,
+        polls: &'a Cell<usize>,
+    }
+
+    impl Future for Counted<'_> {
+        type Output = usize;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<usize> {
+            self.polls.set(self.polls.get() + 1);
+            self.future.as_mut().poll(cx)
+        }
+    }
+
+    let polls = Cell::new(0);
+    let inline = ExecutorFn::new(|future: BoxFuture| {
+        Ok::<_, ()>(Counted {
+            future,
+            polls: &polls,
+        })
+    });
+
+    let mut set = TaskSet::new(inline);
+    for i in 0..100 {
+        set.spawn(Box::pin(async move {
+            if i == 99 {
+                yield_now().await;
+            } else {
+                pending::<()>().await;
+            }
+            i
+        }))
+        .unwrap();
+    }
+
+    // Every task is polled once, then only the one that woke itself.
+    assert_eq!(block_on(set.next()), Some(99));
+    assert_eq!(polls.get(), 101);
+}
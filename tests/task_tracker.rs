@@ -0,0 +1,40 @@
+//! Tests for `TaskTracker`.
+
+#![cfg(feature = "std")]
+
+use futures_lite::future::{block_on, poll_once};
+use futures_task_lite::testing::TestExecutor;
+use futures_task_lite::{DetachableTask, TaskTracker, TrackerError};
+
+use std::pin::pin;
+
+#[test]
+fn test_wait_for_detached() {
+    let tracker = TaskTracker::new();
+    let ex = TestExecutor::new();
+
+    tracker.spawn(&ex, async {}).unwrap().detach();
+    tracker.spawn(&ex, async {}).unwrap().detach();
+    assert_eq!(tracker.len(), 2);
+
+    tracker.close();
+    let mut wait = pin!(tracker.wait());
+    assert!(block_on(poll_once(wait.as_mut())).is_none());
+
+    ex.run_until_stalled();
+    assert!(tracker.is_empty());
+    assert!(block_on(poll_once(wait.as_mut())).is_some());
+}
+
+#[test]
+fn test_closed() {
+    let tracker = TaskTracker::new();
+    let ex = TestExecutor::new();
+    tracker.close();
+
+    assert!(matches!(
+        tracker.spawn(&ex, async {}),
+        Err(TrackerError::Closed)
+    ));
+    block_on(tracker.wait());
+}
@@ -0,0 +1,60 @@
+//! Tests for `TestExecutor`.
+
+#![cfg(feature = "std")]
+
+use futures_lite::future::{block_on, poll_once, yield_now};
+use futures_task_lite::testing::TestExecutor;
+use futures_task_lite::{CancellableTask, Executor};
+
+use std::cell::Cell;
+
+#[test]
+fn test_step() {
+    let counter = Cell::new(0);
+    let ex = TestExecutor::new();
+
+    let mut task = ex
+        .try_spawn(async {
+            counter.set(counter.get() + 1);
+            yield_now().await;
+            counter.set(counter.get() + 1);
+            counter.get()
+        })
+        .unwrap();
+
+    // Nothing runs until the executor is stepped.
+    assert_eq!(counter.get(), 0);
+    assert_eq!(block_on(poll_once(&mut task)), None);
+
+    assert!(ex.step());
+    assert_eq!(counter.get(), 1);
+
+    ex.run_until_stalled();
+    assert!(!ex.step());
+    assert!(ex.is_empty());
+    assert_eq!(block_on(task), 2);
+}
+
+#[test]
+fn test_fail_next() {
+    let ex = TestExecutor::new();
+    ex.fail_next(2);
+
+    assert!(ex.try_spawn(async {}).is_err());
+    assert!(ex.try_spawn(async {}).is_err());
+    assert!(ex.try_spawn(async {}).is_ok());
+}
+
+#[test]
+fn test_cancel() {
+    let ex = TestExecutor::new();
+    let task = ex.try_spawn(std::future::pending::<()>()).unwrap();
+
+    ex.run_until_stalled();
+    assert_eq!(ex.len(), 1);
+
+    let cancel = task.cancel();
+    ex.run_until_stalled();
+    assert!(ex.is_empty());
+    assert_eq!(block_on(cancel), None);
+}
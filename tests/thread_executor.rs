@@ -0,0 +1,60 @@
+//! Tests for `ThreadExecutor`.
+
+#![cfg(all(
+    feature = "std",
+    not(all(target_family = "wasm", not(target_feature = "atomics")))
+))]
+
+use futures_lite::future::{block_on, pending};
+use futures_task_lite::{CancellableTask, DetachableTask, Executor, ThreadExecutor};
+
+use std::sync::mpsc;
+
+#[test]
+fn test_spawn() {
+    let task = ThreadExecutor::default()
+        .try_spawn(async { 1 + 2 })
+        .unwrap();
+    assert_eq!(block_on(task), 3);
+}
+
+#[test]
+fn test_cancel() {
+    let (sender, receiver) = mpsc::channel::<()>();
+
+    let task = ThreadExecutor::default()
+        .try_spawn(async move {
+            let _sender = sender;
+            pending::<()>().await
+        })
+        .unwrap();
+
+    assert_eq!(block_on(task.cancel()), None);
+
+    // The future, and with it the sender, has been dropped.
+    assert!(receiver.recv().is_err());
+}
+
+#[test]
+fn test_detach() {
+    let (sender, receiver) = mpsc::channel();
+
+    ThreadExecutor::default()
+        .try_spawn(async move { sender.send(5).unwrap() })
+        .unwrap()
+        .detach();
+
+    assert_eq!(receiver.recv().unwrap(), 5);
+}
+
+#[test]
+fn test_named() {
+    use futures_task_lite::ExecutorExt;
+
+    let exec = ThreadExecutor::default().named("worker");
+    let task = exec
+        .try_spawn(async { std::thread::current().name().map(String::from) })
+        .unwrap();
+
+    assert_eq!(block_on(task).as_deref(), Some("worker"));
+}
@@ -0,0 +1,42 @@
+//! Tests for the `Timeout` adapter.
+
+#![cfg(feature = "async-io")]
+
+use futures_lite::future::{block_on, pending};
+use futures_task_lite::impls::AsyncIoTimer;
+use futures_task_lite::{Executor, ExecutorExt, TaskExt, ThreadExecutor, Timer};
+
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_async_io_timer() {
+    let start = Instant::now();
+    block_on(AsyncIoTimer::default().sleep(Duration::from_millis(10)));
+    assert!(start.elapsed() >= Duration::from_millis(10));
+}
+
+#[test]
+fn test_timeout() {
+    let exec =
+        ThreadExecutor::default().with_timeout(Duration::from_millis(10), AsyncIoTimer::default());
+
+    let fast = exec.try_spawn(async { 1 }).unwrap();
+    let slow = exec.try_spawn(pending::<()>()).unwrap();
+
+    assert_eq!(block_on(fast), Ok(1));
+    assert!(block_on(slow).is_err());
+}
+
+#[test]
+fn test_task_with_timeout() {
+    let exec = ThreadExecutor::default();
+    let timer = AsyncIoTimer::default();
+
+    let fast = exec.try_spawn(async { 1 }).unwrap();
+    let slow = exec.try_spawn(pending::<()>()).unwrap();
+
+    let fast = fast.timeout(Duration::from_secs(10), &timer);
+    let slow = slow.timeout(Duration::from_millis(10), &timer);
+    assert_eq!(block_on(fast), Ok(1));
+    assert!(block_on(slow).is_err());
+}
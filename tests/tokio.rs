@@ -0,0 +1,77 @@
+//! Tests for the `tokio` integration.
+
+#![cfg(feature = "tokio")]
+
+use futures_task_lite::{CancellableTask, FutureExt};
+
+#[test]
+fn test_fallible_task() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    let ok = async { 1 }.par(rt.handle()).fallible();
+    let panicked = async { panic!("oh no") }.par(rt.handle()).fallible();
+
+    rt.block_on(async {
+        assert_eq!(ok.await.unwrap(), 1);
+        assert!(panicked.await.unwrap_err().is_panic());
+    });
+}
+
+#[test]
+fn test_cancel_finished_task() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    rt.block_on(async {
+        let task = async { 1 }.par(rt.handle());
+        tokio::task::yield_now().await;
+        assert_eq!(task.cancel().await, Some(1));
+
+        let task = std::future::pending::<i32>().par(rt.handle());
+        assert_eq!(task.cancel().await, None);
+    });
+}
+
+#[test]
+fn test_local_set() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let local = tokio::task::LocalSet::new();
+
+    // `Rc` is not `Send`.
+    let value = std::rc::Rc::new(5);
+    let task = async move { *value }.par_local(&local);
+
+    assert_eq!(rt.block_on(local.run_until(task)), 5);
+}
+
+#[cfg(feature = "timer")]
+#[test]
+fn test_timeout() {
+    use futures_task_lite::impls::TokioTimer;
+    use futures_task_lite::ExecutorExt;
+    use std::time::Duration;
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap();
+    let _guard = rt.enter();
+    let exec = rt
+        .handle()
+        .with_timeout(Duration::from_millis(10), TokioTimer::default());
+
+    let fast = exec.try_spawn(async { 1 }).unwrap();
+    let slow = exec
+        .try_spawn(tokio::time::sleep(Duration::from_secs(60)))
+        .unwrap();
+
+    rt.block_on(async {
+        assert_eq!(fast.await, Ok(1));
+        assert!(slow.await.is_err());
+    });
+}
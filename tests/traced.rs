@@ -0,0 +1,133 @@
+//! Tests for the `Traced` executor adapter.
+
+#![cfg(all(feature = "tracing", feature = "async-executor"))]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::block_on;
+use futures_task_lite::adapter::Traced;
+use futures_task_lite::Executor;
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Span, Subscriber};
+use tracing_core::span::Current;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// A span seen by the [`Recorder`].
+struct SpanData {
+    metadata: &'static Metadata<'static>,
+    parent: Option<u64>,
+    task_id: Option<u64>,
+}
+
+#[derive(Default)]
+struct State {
+    spans: HashMap<u64, SpanData>,
+    stack: Vec<u64>,
+}
+
+/// A subscriber that records every span and tracks the current one.
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<State>>);
+
+struct TaskId(Option<u64>);
+
+impl Visit for TaskId {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == "task.id" {
+            self.0 = Some(value);
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn fmt::Debug) {}
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+        let mut state = self.0.lock().unwrap();
+        let parent = match attrs.parent() {
+            Some(parent) => Some(parent.into_u64()),
+            None if attrs.is_contextual() => state.stack.last().copied(),
+            None => None,
+        };
+        let mut task_id = TaskId(None);
+        attrs.record(&mut task_id);
+
+        let id = state.spans.len() as u64 + 1;
+        state.spans.insert(
+            id,
+            SpanData {
+                metadata: attrs.metadata(),
+                parent,
+                task_id: task_id.0,
+            },
+        );
+        Id::from_u64(id)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, span: &Id) {
+        self.0.lock().unwrap().stack.push(span.into_u64());
+    }
+
+    fn exit(&self, _span: &Id) {
+        self.0.lock().unwrap().stack.pop();
+    }
+
+    fn current_span(&self) -> Current {
+        let state = self.0.lock().unwrap();
+        match state.stack.last() {
+            Some(&id) => Current::new(Id::from_u64(id), state.spans[&id].metadata),
+            None => Current::none(),
+        }
+    }
+}
+
+/// Spawn a future that reports the span it runs in, from inside an `outer` span.
+fn spawn_in_outer(traced: &Traced<&LocalExecutor<'_>>) -> (Id, Option<Id>) {
+    let outer = tracing::info_span!("outer");
+    let task = outer.in_scope(|| traced.try_spawn(async { Span::current().id() }).unwrap());
+
+    // The task runs after the outer span has been exited.
+    let current = block_on(traced.get_ref().run(task));
+    (outer.id().unwrap(), current)
+}
+
+#[test]
+fn test_caller_span() {
+    let recorder = Recorder::default();
+    let ex = LocalExecutor::new();
+
+    tracing::subscriber::with_default(recorder, || {
+        let (outer, current) = spawn_in_outer(&Traced::new(&ex));
+        assert_eq!(current, Some(outer));
+    });
+}
+
+#[test]
+fn test_task_spans() {
+    let recorder = Recorder::default();
+    let ex = LocalExecutor::new();
+
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let (outer, current) = spawn_in_outer(&Traced::new(&ex).task_spans(true));
+
+        let state = recorder.0.lock().unwrap();
+        let span = &state.spans[&current.unwrap().into_u64()];
+        assert_eq!(span.metadata.name(), "task");
+        assert_eq!(span.parent, Some(outer.into_u64()));
+        assert!(span.task_id.is_some());
+    });
+}
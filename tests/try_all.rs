@@ -0,0 +1,93 @@
+//! Tests for `try_all`.
+
+#![cfg(all(feature = "ext", feature = "async-executor"))]
+
+use async_executor_crate::LocalExecutor;
+use futures_lite::future::{block_on, yield_now};
+use futures_task_lite::{try_all, Executor};
+
+use std::cell::Cell;
+use std::future::Future;
+
+#[test]
+fn test_try_all_ok() {
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let futures = (1..=3).map(|x| async move { Ok::<_, ()>(x) });
+        let results = try_all(&ex, futures).await.unwrap();
+
+        assert_eq!(results, Ok(vec![1, 2, 3]));
+    }));
+}
+
+#[test]
+fn test_try_all_short_circuits() {
+    let ex = LocalExecutor::new();
+
+    block_on(ex.run(async {
+        let futures = [false, true].map(|fail| async move {
+            if fail {
+                return Err("failed");
+            }
+
+            // Never finishes unless cancelled.
+            loop {
+                yield_now().await;
+            }
+        });
+        let results = try_all(&ex, futures).await.unwrap();
+
+        assert_eq!(results, Err::<Vec<()>, _>("failed"));
+    }));
+}
+
+#[test]
+fn test_try_all_spawns_in_one_batch() {
+    struct Batching<'a, 'b> {
+        ex: &'b LocalExecutor<'a>,
+        batches: Cell<usize>,
+    }
+
+    impl<'a, F: Future + 'a> Executor<F> for Batching<'a, '_> {
+        type Task = <LocalExecutor<'a> as Executor<F>>::Task;
+        type Error = <LocalExecutor<'a> as Executor<F>>::Error;
+
+        fn try_spawn(&self, future: F) -> Result<Self::Task, Self::Error> {
+            self.ex.try_spawn(future)
+        }
+
+        fn try_spawn_all(&self, futures: Vec<F>) -> Result<Vec<Self::Task>, Self::Error> {
+            self.batches.set(self.batches.get() + 1);
+            futures.into_iter().map(|f| self.ex.try_spawn(f)).collect()
+        }
+    }
+
+    let ex = LocalExecutor::new();
+    let batching = Batching {
+        ex: &ex,
+        batches: Cell::new(0),
+    };
+
+    block_on(ex.run(async {
+        let futures = (1..=3).map(|x| async move { Ok::<_, ()>(x) });
+        let results = try_all(&batching, futures).await.unwrap();
+
+        assert_eq!(results, Ok(vec![1, 2, 3]));
+    }));
+    assert_eq!(batching.batches.get(), 1);
+}
+
+#[test]
+fn test_try_all_pinned_tasks() {
+    use futures_task_lite::adapter::ExecutorFn;
+
+    // An executor whose "tasks" are the futures themselves, which are not `Unpin`.
+    let inline = ExecutorFn::new(Ok::<_, ()>);
+    let futures = (0..3).map(|i| async move {
+        yield_now().await;
+        Ok::<_, ()>(i)
+    });
+
+    assert_eq!(block_on(try_all(inline, futures)), Ok(Ok(vec![0, 1, 2])));
+}
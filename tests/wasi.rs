@@ -0,0 +1,38 @@
+//! Tests for the single-threaded WASI executor.
+
+#![cfg(feature = "wasi")]
+
+use futures_lite::future::yield_now;
+use futures_task_lite::{FutureExt, WasiExecutor};
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[test]
+fn test_block_on() {
+    let ex = WasiExecutor::new();
+    let counter = Rc::new(Cell::new(0));
+
+    let tasks = (0..3)
+        .map(|i| {
+            let counter = counter.clone();
+            async move {
+                yield_now().await;
+                counter.set(counter.get() + 1);
+                i
+            }
+            .par_local(&ex)
+        })
+        .collect::<Vec<_>>();
+
+    let sum = ex.block_on(async {
+        let mut sum = 0;
+        for task in tasks {
+            sum += task.await;
+        }
+        sum
+    });
+
+    assert_eq!(sum, 3);
+    assert_eq!(counter.get(), 3);
+}
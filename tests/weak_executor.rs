@@ -0,0 +1,23 @@
+//! Tests for `WeakExecutor`.
+
+#![cfg(feature = "async-executor")]
+
+use async_executor_crate::Executor as AsyncExecutor;
+use futures_lite::future::block_on;
+use futures_task_lite::{Executor, WeakError, WeakExecutor};
+
+use std::sync::Arc;
+
+#[test]
+fn test_weak_executor() {
+    let ex = Arc::new(AsyncExecutor::new());
+    let weak = WeakExecutor::new(&ex);
+
+    let task = weak.try_spawn(async { 7 }).unwrap();
+    assert_eq!(block_on(ex.run(task)), 7);
+
+    // Once the executor is dropped, spawning fails.
+    drop(ex);
+    assert!(weak.upgrade().is_none());
+    assert!(matches!(weak.try_spawn(async {}), Err(WeakError::Gone(_))));
+}